@@ -33,3 +33,43 @@ fn backtrace_contains_function_names() {
         .map(|n| n.to_string());
     assert!(names.any(|n| n.contains("::example::")));
 }
+
+mod lazy {
+    use snafu::{prelude::*, ErrorCompat, LazyBacktrace};
+
+    #[derive(Debug, Snafu)]
+    struct LazyError {
+        backtrace: LazyBacktrace,
+    }
+
+    fn lazy_example() -> Result<(), LazyError> {
+        LazySnafu.fail()
+    }
+
+    #[test]
+    fn backtrace_is_resolved_when_used() {
+        let error = lazy_example().unwrap_err();
+        let backtrace = ErrorCompat::backtrace(&error).unwrap();
+        let mut names = backtrace
+            .frames()
+            .iter()
+            .flat_map(|f| f.symbols())
+            .flat_map(|s| s.name())
+            .map(|n| n.to_string());
+        assert!(names.any(|n| n.contains("::lazy_example::")));
+    }
+
+    #[test]
+    fn backtrace_can_be_resolved_by_several_threads() {
+        let error = lazy_example().unwrap_err();
+
+        let frames: Vec<_> = std::thread::scope(|s| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| s.spawn(|| ErrorCompat::backtrace(&error).unwrap().frames().len()))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        assert!(frames.iter().all(|&n| n > 0 && n == frames[0]));
+    }
+}
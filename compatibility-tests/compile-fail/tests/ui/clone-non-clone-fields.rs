@@ -0,0 +1,17 @@
+use snafu::{prelude::*, Backtrace};
+
+#[derive(Debug, Snafu)]
+#[snafu(clone)]
+enum Error {
+    Read { source: std::io::Error },
+
+    Parse { backtrace: Backtrace },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(clone)]
+struct StructError {
+    source: std::io::Error,
+}
+
+fn main() {}
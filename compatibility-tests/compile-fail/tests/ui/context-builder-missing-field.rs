@@ -0,0 +1,22 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(context(builder))]
+    Leaf {
+        id: u8,
+        name: String,
+        #[snafu(default)]
+        retries: u8,
+    },
+}
+
+fn no_fields_set() -> Result<(), Error> {
+    LeafSnafu::new().fail()
+}
+
+fn one_field_missing() -> Result<(), Error> {
+    LeafSnafu::new().id(1).retries(3).fail()
+}
+
+fn main() {}
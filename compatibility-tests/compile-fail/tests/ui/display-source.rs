@@ -0,0 +1,32 @@
+extern crate snafu;
+
+use snafu::prelude::*;
+
+type BoxError = Box<dyn std::error::Error>;
+
+#[derive(Debug, Snafu)]
+#[snafu(display_source(never))]
+enum Error {
+    #[snafu(display("Could not load: {source}"))]
+    Shorthand { source: BoxError },
+
+    #[snafu(display("Could not load: {}", source))]
+    Positional { source: BoxError },
+
+    #[snafu(display("Could not load: {}", source.to_string()))]
+    Expression { source: BoxError },
+
+    /// Could not load: {source}
+    DocComment { source: BoxError },
+
+    #[snafu(display_source(append))]
+    OnVariant { source: BoxError },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display_source(sometimes))]
+struct UnknownPolicy {
+    source: BoxError,
+}
+
+fn main() {}
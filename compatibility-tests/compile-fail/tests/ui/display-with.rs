@@ -0,0 +1,16 @@
+extern crate snafu;
+
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(display_with = snafu::fmt::bytes)]
+enum Error {
+    #[snafu(display("Could not read {size}"))]
+    Read {
+        #[snafu(display_with = snafu::fmt::bytes)]
+        source: std::io::Error,
+        size: u64,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+extern crate snafu;
+
+use snafu::prelude::*;
+
+type BoxError = Box<dyn std::error::Error>;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(from)]
+    MissingSource {},
+
+    #[snafu(from)]
+    MissingDefault {
+        source: BoxError,
+        a: i32,
+        #[snafu(default)]
+        b: i32,
+    },
+
+    #[snafu(from, context(false))]
+    WithoutContext { source: BoxError },
+
+    #[snafu(from, whatever, display("{message}"))]
+    Whatever { message: String },
+}
+
+fn main() {}
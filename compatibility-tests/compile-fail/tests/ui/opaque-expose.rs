@@ -0,0 +1,18 @@
+extern crate snafu;
+
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+struct InnerError;
+
+#[derive(Debug, Snafu)]
+#[snafu(opaque(expose(kind)))]
+struct MissingReturnType(InnerError);
+
+#[derive(Debug, Snafu)]
+#[snafu(opaque(expose(kind -> u8)))]
+enum NotATupleStruct {
+    Alpha,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+#[snafu(partial_eq(eq))]
+enum NotEqError {
+    Measure { value: f64 },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(partial_eq(total))]
+struct UnknownArgumentError;
+
+fn main() {}
@@ -0,0 +1,13 @@
+extern crate snafu;
+
+use snafu::prelude::*;
+
+#[snafu::wrap(std::io::Error)]
+#[derive(Debug, Snafu)]
+struct NotAnEnum;
+
+#[snafu::wrap(&'static std::io::Error)]
+#[derive(Debug, Snafu)]
+enum UnnameableType {}
+
+fn main() {}
@@ -5,9 +5,18 @@
 use snafu::prelude::*;
 
 #[derive(Debug, Snafu)]
-#[snafu(visibility(pub))]
+#[snafu(visibility(pub), catalog)]
 /// Enum docs
 pub enum Error {
     /// Variant docs
     Variant,
 }
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub), context(builder))]
+/// Builder docs
+pub struct BuilderError {
+    id: u8,
+    #[snafu(default)]
+    hint: Option<u8>,
+}
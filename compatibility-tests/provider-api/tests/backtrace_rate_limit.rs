@@ -0,0 +1,43 @@
+#![feature(error_generic_member_access)]
+
+use snafu::{
+    backtrace::{self, Policy},
+    prelude::*,
+    Backtrace, IntoError,
+};
+use std::time::Duration;
+
+#[derive(Debug, Snafu)]
+struct InnerError {
+    backtrace: Backtrace,
+}
+
+#[derive(Debug, Snafu)]
+struct OuterError {
+    source: Box<dyn std::error::Error + Send + Sync>,
+    backtrace: Option<Backtrace>,
+}
+
+fn wrap(source: Box<dyn std::error::Error + Send + Sync>) -> OuterError {
+    OuterSnafu.into_error(source)
+}
+
+// The policy is global, so this is the only test in this binary.
+#[test]
+fn sources_with_a_backtrace_do_not_use_up_the_rate_limit() {
+    backtrace::set_capture(Policy::RateLimited {
+        limit: 1,
+        window: Duration::from_secs(3600),
+    });
+
+    for _ in 0..2 {
+        let e = wrap(Box::new(InnerSnafu.build()));
+        assert!(e.backtrace.is_none());
+    }
+
+    let e = wrap(Box::new(std::fmt::Error));
+    assert!(e.backtrace.is_some());
+
+    let e = wrap(Box::new(std::fmt::Error));
+    assert!(e.backtrace.is_none());
+}
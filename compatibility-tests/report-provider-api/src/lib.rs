@@ -89,3 +89,42 @@ fn provided_location_is_printed() {
     assert!(msg.contains(&l_b), "Expected {msg:?} to contain {l_b}");
     assert!(msg.contains(&l_c), "Expected {msg:?} to contain {l_c}");
 }
+
+#[test]
+fn most_severe_severity_is_converted_to_exit_code() {
+    use snafu::{IntoError, Severity};
+    use std::process::Termination;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(severity(Fatal))]
+    struct InnerError;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(severity(Warning))]
+    enum OuterError {
+        Wrapped { source: InnerError },
+        Alone,
+    }
+
+    let e = WrappedSnafu.into_error(InnerSnafu.build());
+    assert_eq!(
+        snafu::error::request_value::<Severity>(&e),
+        Some(Severity::Warning)
+    );
+    assert_eq!(Report::from_error(e).report(), ExitCode::from(2));
+
+    let warning = Report::from_error(AloneSnafu.build()).report();
+    assert_eq!(warning, ExitCode::FAILURE);
+}
+
+#[test]
+fn provided_exit_code_is_preferred_over_severity() {
+    use std::process::Termination;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(severity(Fatal), provide(ExitCode => ExitCode::from(7)))]
+    struct Error;
+
+    let code = Report::from_error(Snafu.build()).report();
+    assert_eq!(code, ExitCode::from(7));
+}
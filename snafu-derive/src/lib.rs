@@ -3,7 +3,7 @@
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{quote, ToTokens as _};
 use std::collections::BTreeSet;
 
 mod parse;
@@ -26,6 +26,15 @@ pub fn report(attr: TokenStream, item: TokenStream) -> TokenStream {
         .into()
 }
 
+mod wrap;
+
+#[proc_macro_attribute]
+pub fn wrap(attr: TokenStream, item: TokenStream) -> TokenStream {
+    wrap::body(attr, item)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
 /// Some arbitrary tokens we treat as a black box
 type UserInput = Box<dyn quote::ToTokens>;
 
@@ -48,6 +57,11 @@ struct EnumInfo {
     default_visibility: Option<UserInput>,
     default_suffix: SuffixKind,
     module: Option<ModuleName>,
+    has_catalog: bool,
+    has_severity: bool,
+    is_cloneable: bool,
+    is_eq: bool,
+    is_partial_eq: bool,
 }
 
 /// A struct or enum variant, with named fields.
@@ -58,10 +72,13 @@ struct FieldContainer {
     selector_kind: ContextSelectorKind,
     display_format: Option<Display>,
     doc_comment: Option<DocComment>,
+    exit_code: Option<syn::Expr>,
     visibility: Option<UserInput>,
     module: Option<ModuleName>,
     provides: Vec<Provide>,
     is_transparent: bool,
+    severity: Option<syn::Ident>,
+    display_source: Option<DisplaySourcePolicy>,
 }
 
 impl FieldContainer {
@@ -72,6 +89,99 @@ impl FieldContainer {
     fn provides(&self) -> &[Provide] {
         &self.provides
     }
+
+    /// The types of the fields compared by `#[snafu(partial_eq)]`.
+    fn compared_field_types(&self) -> Vec<&syn::Type> {
+        self.user_fields()
+            .iter()
+            .chain(self.selector_kind.message_field())
+            .map(|f| &f.ty)
+            .collect()
+    }
+
+    /// The formatting function of a field, if it has one.
+    fn display_with(&self, name: &syn::Ident) -> Option<&syn::Expr> {
+        self.user_fields()
+            .iter()
+            .chain(&self.backtrace_field)
+            .chain(&self.implicit_fields)
+            .chain(self.selector_kind.message_field())
+            .find(|f| f.name == *name)?
+            .display_with
+            .as_ref()
+    }
+
+    /// The parts of the display format that use the field.
+    fn display_uses<'a>(&'a self, name: &'a syn::Ident) -> Vec<&'a dyn quote::ToTokens> {
+        let mut uses: Vec<&dyn quote::ToTokens> = Vec::new();
+
+        if let Some(display) = &self.display_format {
+            let mut exprs = display.exprs.iter();
+
+            if let Some(format) = exprs.next() {
+                if display.shorthand_names.contains(name) {
+                    uses.push(format);
+                }
+            }
+
+            uses.extend(
+                exprs
+                    .filter(|arg| mentions_ident(arg.to_token_stream(), name))
+                    .map(|arg| arg as &dyn quote::ToTokens),
+            );
+        } else if let Some(doc_comment) = &self.doc_comment {
+            if doc_comment.shorthand_names.contains(name) {
+                uses.push(name);
+            }
+        }
+
+        uses
+    }
+
+    /// The source field whose message is added to the end of the
+    /// `Display` output. Nothing is added when the display format
+    /// already uses the source field. The optional source of a
+    /// `whatever` error is only added when it is present.
+    fn appended_source(&self) -> Option<&SourceField> {
+        match (&self.display_source, &self.selector_kind) {
+            (_, _) if self.is_transparent => None,
+            (Some(DisplaySourcePolicy::Append), selector_kind) => selector_kind
+                .source_field()
+                .filter(|f| self.display_uses(f.name()).is_empty()),
+            _ => None,
+        }
+    }
+
+    /// Whether the `Display` output contains the message of the
+    /// source error, when that is known.
+    fn display_includes_source(&self) -> Option<bool> {
+        match (&self.display_source, &self.selector_kind) {
+            (_, _) if self.is_transparent => None,
+            (Some(DisplaySourcePolicy::Append), selector_kind) => {
+                Some(selector_kind.source_field().is_some())
+            }
+            (Some(DisplaySourcePolicy::Never), _) => Some(false),
+            (None, _) => None,
+        }
+    }
+}
+
+fn mentions_ident(tokens: proc_macro2::TokenStream, ident: &syn::Ident) -> bool {
+    use proc_macro2::TokenTree;
+
+    tokens.into_iter().any(|tt| match tt {
+        TokenTree::Ident(i) => i == *ident,
+        TokenTree::Group(g) => mentions_ident(g.stream(), ident),
+        _ => false,
+    })
+}
+
+/// How the message of the source error relates to the `Display`
+/// output of the error wrapping it.
+#[derive(Copy, Clone, PartialEq)]
+enum DisplaySourcePolicy {
+    Append,
+    Never,
 }
 
 struct Provide {
@@ -136,6 +246,8 @@ enum ContextSelectorKind {
         selector_name: ContextSelectorName,
         source_field: Option<SourceField>,
         user_fields: Vec<Field>,
+        is_builder: bool,
+        from_source: bool,
     },
 
     Whatever {
@@ -153,6 +265,16 @@ impl ContextSelectorKind {
         matches!(self, ContextSelectorKind::Whatever { .. })
     }
 
+    fn is_builder(&self) -> bool {
+        matches!(
+            self,
+            ContextSelectorKind::Context {
+                is_builder: true,
+                ..
+            }
+        )
+    }
+
     fn user_fields(&self) -> &[Field] {
         match self {
             ContextSelectorKind::Context { user_fields, .. } => user_fields,
@@ -191,21 +313,35 @@ struct NamedStructInfo {
     crate_root: UserInput,
     field_container: FieldContainer,
     generics: syn::Generics,
+    has_catalog: bool,
+    has_severity: bool,
+    is_cloneable: bool,
+    is_eq: bool,
+    is_partial_eq: bool,
 }
 
 struct TupleStructInfo {
     crate_root: UserInput,
+    exposed: Vec<ExposedMethod>,
     name: syn::Ident,
     generics: syn::Generics,
     transformation: Transformation,
     provides: Vec<Provide>,
 }
 
+/// A method of the inner error that an opaque error forwards to.
+struct ExposedMethod {
+    name: syn::Ident,
+    ty: syn::Type,
+}
+
 #[derive(Clone)]
 pub(crate) struct Field {
     name: syn::Ident,
     ty: syn::Type,
     provide: bool,
+    default: Option<syn::Expr>,
+    display_with: Option<syn::Expr>,
     original: syn::Field,
 }
 
@@ -226,6 +362,7 @@ struct SourceField {
     transformation: Transformation,
     backtrace_delegate: bool,
     provide: bool,
+    is_shared: bool,
 }
 
 impl SourceField {
@@ -336,8 +473,19 @@ fn parse_snafu_struct(
 
 struct Display {
     exprs: Vec<syn::Expr>,
+    key: Option<syn::LitStr>,
     shorthand_names: BTreeSet<syn::Ident>,
     assigned_names: BTreeSet<syn::Ident>,
+    placeholder_format: Option<syn::LitStr>,
+    placeholders: Vec<DisplayWithPlaceholder>,
+}
+
+/// A `{field:with(function)}` in a format string, which has been
+/// replaced by `{placeholder}`.
+struct DisplayWithPlaceholder {
+    placeholder: syn::Ident,
+    field: syn::Ident,
+    function: proc_macro2::TokenStream,
 }
 
 #[derive(Default)]
@@ -425,6 +573,31 @@ impl EnumInfo {
         let display_impl = DisplayImpl(&self);
         let error_impl = ErrorImpl(&self);
         let error_compat_impl = ErrorCompatImpl(&self);
+        let clone_impl = if self.is_cloneable {
+            Some(CloneImpl(&self))
+        } else {
+            None
+        };
+        let partial_eq_impl = if self.is_partial_eq {
+            Some(PartialEqImpl(&self))
+        } else {
+            None
+        };
+        let catalog_impl = if self.has_catalog {
+            Some(CatalogImpl(&self))
+        } else {
+            None
+        };
+        let severity_impl = if self.has_severity {
+            Some(SeverityImpl(&self))
+        } else {
+            None
+        };
+        let exit_code_impl = if self.variants.iter().any(|v| v.exit_code.is_some()) {
+            Some(ExitCodeProviderImpl(&self))
+        } else {
+            None
+        };
 
         let context = match &self.module {
             None => quote! { #context_selectors },
@@ -447,6 +620,11 @@ impl EnumInfo {
             #display_impl
             #error_impl
             #error_compat_impl
+            #clone_impl
+            #partial_eq_impl
+            #catalog_impl
+            #severity_impl
+            #exit_code_impl
         }
     }
 }
@@ -529,6 +707,7 @@ impl<'a> quote::ToTokens for ContextSelector<'a> {
             visibility: selector_visibility,
             where_clauses: &self.0.provided_where_clauses(),
             default_suffix,
+            with_defaults: false,
         };
 
         stream.extend(quote! { #context_selector });
@@ -557,6 +736,7 @@ impl<'a> quote::ToTokens for DisplayImpl<'a> {
                 } = variant;
 
                 let arm = DisplayMatchArm {
+                    crate_root: &self.0.crate_root,
                     field_container: variant,
                     default_name: &variant_name,
                     display_format: display_format.as_ref(),
@@ -633,7 +813,85 @@ struct ErrorCompatImpl<'a>(&'a EnumInfo);
 
 impl<'a> quote::ToTokens for ErrorCompatImpl<'a> {
     fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
-        use self::shared::{ErrorCompat, ErrorCompatBacktraceMatchArm};
+        use self::shared::{
+            ErrorCompat, ErrorCompatBacktraceMatchArm, ErrorCompatDeepestBacktraceMatchArm,
+            ErrorCompatDisplaySourceMatchArm, ErrorCompatLocationMatchArm,
+            ErrorCompatLocationTraceMatchArm,
+        };
+
+        let variants_to_location: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .map(|field_container| {
+                let crate_root = &self.0.crate_root;
+                let enum_name = &self.0.name;
+                let variant_name = &field_container.name;
+
+                let match_arm = ErrorCompatLocationMatchArm {
+                    field_container,
+                    crate_root,
+                    pattern_ident: &quote! { #enum_name::#variant_name },
+                };
+
+                quote! { #match_arm }
+            })
+            .collect();
+
+        let variants_to_location_trace: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .map(|field_container| {
+                let crate_root = &self.0.crate_root;
+                let enum_name = &self.0.name;
+                let variant_name = &field_container.name;
+
+                let match_arm = ErrorCompatLocationTraceMatchArm {
+                    field_container,
+                    crate_root,
+                    pattern_ident: &quote! { #enum_name::#variant_name },
+                };
+
+                quote! { #match_arm }
+            })
+            .collect();
+
+        let variants_to_display_source: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .map(|field_container| {
+                let enum_name = &self.0.name;
+                let variant_name = &field_container.name;
+
+                let match_arm = ErrorCompatDisplaySourceMatchArm {
+                    field_container,
+                    pattern_ident: &quote! { #enum_name::#variant_name },
+                };
+
+                quote! { #match_arm }
+            })
+            .collect();
+
+        let variants_to_deepest_backtrace: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .map(|field_container| {
+                let crate_root = &self.0.crate_root;
+                let enum_name = &self.0.name;
+                let variant_name = &field_container.name;
+
+                let match_arm = ErrorCompatDeepestBacktraceMatchArm {
+                    field_container,
+                    crate_root,
+                    pattern_ident: &quote! { #enum_name::#variant_name },
+                };
+
+                quote! { #match_arm }
+            })
+            .collect();
 
         let variants_to_backtrace: Vec<_> = self
             .0
@@ -658,6 +916,10 @@ impl<'a> quote::ToTokens for ErrorCompatImpl<'a> {
             crate_root: &self.0.crate_root,
             parameterized_error_name: &self.0.parameterized_name(),
             backtrace_arms: &variants_to_backtrace,
+            location_arms: &variants_to_location,
+            location_trace_arms: &variants_to_location_trace,
+            display_source_arms: &variants_to_display_source,
+            deepest_backtrace_arms: &variants_to_deepest_backtrace,
             original_generics: shared::GenericsWithoutDefaults::new(&self.0.generics),
             where_clauses: &self.0.provided_where_clauses(),
         };
@@ -668,6 +930,204 @@ impl<'a> quote::ToTokens for ErrorCompatImpl<'a> {
     }
 }
 
+struct CloneImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for CloneImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use self::shared::{Clone, CloneMatchArm};
+
+        let arms: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .map(|field_container| {
+                let enum_name = &self.0.name;
+                let variant_name = &field_container.name;
+
+                let match_arm = CloneMatchArm {
+                    crate_root: &self.0.crate_root,
+                    field_container,
+                    pattern_ident: &quote! { #enum_name::#variant_name },
+                };
+
+                quote! { #match_arm }
+            })
+            .collect();
+
+        let clone_impl = Clone {
+            arms: &arms,
+            original_generics: shared::GenericsWithoutDefaults::new(&self.0.generics),
+            parameterized_error_name: &self.0.parameterized_name(),
+            where_clauses: &self.0.provided_where_clauses(),
+        };
+
+        let clone_impl = quote! { #clone_impl };
+
+        stream.extend(clone_impl);
+    }
+}
+
+struct PartialEqImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for PartialEqImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use self::shared::{PartialEq, PartialEqMatchArm};
+
+        let arms: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .map(|field_container| {
+                let enum_name = &self.0.name;
+                let variant_name = &field_container.name;
+
+                let match_arm = PartialEqMatchArm {
+                    field_container,
+                    pattern_ident: &quote! { #enum_name::#variant_name },
+                };
+
+                quote! { #match_arm }
+            })
+            .collect();
+
+        let eq_field_types: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .flat_map(FieldContainer::compared_field_types)
+            .collect();
+
+        let partial_eq_impl = PartialEq {
+            crate_root: &self.0.crate_root,
+            arms: &arms,
+            eq_field_types: if self.0.is_eq {
+                Some(&eq_field_types)
+            } else {
+                None
+            },
+            original_generics: shared::GenericsWithoutDefaults::new(&self.0.generics),
+            parameterized_error_name: &self.0.parameterized_name(),
+            where_clauses: &self.0.provided_where_clauses(),
+        };
+
+        let partial_eq_impl = quote! { #partial_eq_impl };
+
+        stream.extend(partial_eq_impl);
+    }
+}
+
+struct CatalogImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for CatalogImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use self::shared::{Catalog, CatalogEntry};
+
+        let entries: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .map(|field_container| {
+                let entry = CatalogEntry {
+                    crate_root: &self.0.crate_root,
+                    field_container,
+                    type_name: &self.0.name,
+                    variant_name: Some(&field_container.name),
+                };
+
+                quote! { #entry }
+            })
+            .collect();
+
+        let catalog_impl = Catalog {
+            crate_root: &self.0.crate_root,
+            entries: &entries,
+            original_generics: shared::GenericsWithoutDefaults::new(&self.0.generics),
+            parameterized_error_name: &self.0.parameterized_name(),
+            where_clauses: &self.0.provided_where_clauses(),
+        };
+
+        let catalog_impl = quote! { #catalog_impl };
+
+        stream.extend(catalog_impl);
+    }
+}
+
+struct SeverityImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for SeverityImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use self::shared::{Severity, SeverityMatchArm};
+
+        let arms: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .map(|field_container| {
+                let enum_name = &self.0.name;
+                let variant_name = &field_container.name;
+
+                let match_arm = SeverityMatchArm {
+                    crate_root: &self.0.crate_root,
+                    field_container,
+                    pattern_ident: &quote! { #enum_name::#variant_name },
+                };
+
+                quote! { #match_arm }
+            })
+            .collect();
+
+        let severity_impl = Severity {
+            crate_root: &self.0.crate_root,
+            arms: &arms,
+            original_generics: shared::GenericsWithoutDefaults::new(&self.0.generics),
+            parameterized_error_name: &self.0.parameterized_name(),
+            where_clauses: &self.0.provided_where_clauses(),
+        };
+
+        let severity_impl = quote! { #severity_impl };
+
+        stream.extend(severity_impl);
+    }
+}
+
+struct ExitCodeProviderImpl<'a>(&'a EnumInfo);
+
+impl<'a> quote::ToTokens for ExitCodeProviderImpl<'a> {
+    fn to_tokens(&self, stream: &mut proc_macro2::TokenStream) {
+        use self::shared::{ExitCodeProvider, ExitCodeProviderMatchArm};
+
+        let arms: Vec<_> = self
+            .0
+            .variants
+            .iter()
+            .map(|field_container| {
+                let enum_name = &self.0.name;
+                let variant_name = &field_container.name;
+
+                let match_arm = ExitCodeProviderMatchArm {
+                    crate_root: &self.0.crate_root,
+                    field_container,
+                    pattern_ident: &quote! { #enum_name::#variant_name },
+                };
+
+                quote! { #match_arm }
+            })
+            .collect();
+
+        let exit_code_impl = ExitCodeProvider {
+            crate_root: &self.0.crate_root,
+            arms: &arms,
+            original_generics: shared::GenericsWithoutDefaults::new(&self.0.generics),
+            parameterized_error_name: &self.0.parameterized_name(),
+            where_clauses: &self.0.provided_where_clauses(),
+        };
+
+        let exit_code_impl = quote! { #exit_code_impl };
+
+        stream.extend(exit_code_impl);
+    }
+}
+
 impl NamedStructInfo {
     fn generate_snafu(self) -> proc_macro2::TokenStream {
         let parameterized_struct_name = self.parameterized_name();
@@ -676,6 +1136,11 @@ impl NamedStructInfo {
 
         let Self {
             crate_root,
+            has_catalog,
+            has_severity,
+            is_cloneable,
+            is_eq,
+            is_partial_eq,
             field_container:
                 FieldContainer {
                     name,
@@ -721,7 +1186,11 @@ impl NamedStructInfo {
         };
         let error_impl = quote! { #error_impl };
 
-        use self::shared::{ErrorCompat, ErrorCompatBacktraceMatchArm};
+        use self::shared::{
+            ErrorCompat, ErrorCompatBacktraceMatchArm, ErrorCompatDeepestBacktraceMatchArm,
+            ErrorCompatDisplaySourceMatchArm, ErrorCompatLocationMatchArm,
+            ErrorCompatLocationTraceMatchArm,
+        };
 
         let match_arm = ErrorCompatBacktraceMatchArm {
             field_container,
@@ -730,10 +1199,41 @@ impl NamedStructInfo {
         };
         let match_arm = quote! { #match_arm };
 
+        let location_arm = ErrorCompatLocationMatchArm {
+            field_container,
+            crate_root: &crate_root,
+            pattern_ident: &quote! { Self },
+        };
+        let location_arm = quote! { #location_arm };
+
+        let location_trace_arm = ErrorCompatLocationTraceMatchArm {
+            field_container,
+            crate_root: &crate_root,
+            pattern_ident: &quote! { Self },
+        };
+        let location_trace_arm = quote! { #location_trace_arm };
+
+        let display_source_arm = ErrorCompatDisplaySourceMatchArm {
+            field_container,
+            pattern_ident: &quote! { Self },
+        };
+        let display_source_arm = quote! { #display_source_arm };
+
+        let deepest_backtrace_arm = ErrorCompatDeepestBacktraceMatchArm {
+            field_container,
+            crate_root: &crate_root,
+            pattern_ident: &quote! { Self },
+        };
+        let deepest_backtrace_arm = quote! { #deepest_backtrace_arm };
+
         let error_compat_impl = ErrorCompat {
             crate_root: &crate_root,
             parameterized_error_name: &parameterized_struct_name,
             backtrace_arms: &[match_arm],
+            location_arms: &[location_arm],
+            location_trace_arms: &[location_trace_arm],
+            display_source_arms: &[display_source_arm],
+            deepest_backtrace_arms: &[deepest_backtrace_arm],
             original_generics,
             where_clauses: &where_clauses,
         };
@@ -741,6 +1241,7 @@ impl NamedStructInfo {
         use crate::shared::{Display, DisplayMatchArm};
 
         let arm = DisplayMatchArm {
+            crate_root: &crate_root,
             field_container,
             default_name: &name,
             display_format: display_format.as_ref(),
@@ -757,6 +1258,123 @@ impl NamedStructInfo {
             where_clauses: &where_clauses,
         };
 
+        let clone_impl = if *is_cloneable {
+            use crate::shared::{Clone, CloneMatchArm};
+
+            let arm = CloneMatchArm {
+                crate_root,
+                field_container,
+                pattern_ident: &quote! { Self },
+            };
+            let arm = quote! { #arm };
+
+            let clone_impl = Clone {
+                arms: &[arm],
+                original_generics,
+                parameterized_error_name: &parameterized_struct_name,
+                where_clauses: &where_clauses,
+            };
+
+            Some(quote! { #clone_impl })
+        } else {
+            None
+        };
+
+        let partial_eq_impl = if *is_partial_eq {
+            use crate::shared::{PartialEq, PartialEqMatchArm};
+
+            let arm = PartialEqMatchArm {
+                field_container,
+                pattern_ident: &quote! { Self },
+            };
+            let arm = quote! { #arm };
+
+            let eq_field_types = field_container.compared_field_types();
+
+            let partial_eq_impl = PartialEq {
+                crate_root: &crate_root,
+                arms: &[arm],
+                eq_field_types: if *is_eq { Some(&eq_field_types) } else { None },
+                original_generics,
+                parameterized_error_name: &parameterized_struct_name,
+                where_clauses: &where_clauses,
+            };
+
+            Some(quote! { #partial_eq_impl })
+        } else {
+            None
+        };
+
+        let catalog_impl = if *has_catalog {
+            use crate::shared::{Catalog, CatalogEntry};
+
+            let entry = CatalogEntry {
+                crate_root: &crate_root,
+                field_container,
+                type_name: name,
+                variant_name: None,
+            };
+            let entry = quote! { #entry };
+
+            let catalog_impl = Catalog {
+                crate_root: &crate_root,
+                entries: &[entry],
+                original_generics,
+                parameterized_error_name: &parameterized_struct_name,
+                where_clauses: &where_clauses,
+            };
+
+            Some(quote! { #catalog_impl })
+        } else {
+            None
+        };
+
+        let severity_impl = if *has_severity {
+            use crate::shared::{Severity, SeverityMatchArm};
+
+            let arm = SeverityMatchArm {
+                crate_root: &crate_root,
+                field_container,
+                pattern_ident: &quote! { Self },
+            };
+            let arm = quote! { #arm };
+
+            let severity_impl = Severity {
+                crate_root: &crate_root,
+                arms: &[arm],
+                original_generics,
+                parameterized_error_name: &parameterized_struct_name,
+                where_clauses: &where_clauses,
+            };
+
+            Some(quote! { #severity_impl })
+        } else {
+            None
+        };
+
+        let exit_code_impl = if field_container.exit_code.is_some() {
+            use crate::shared::{ExitCodeProvider, ExitCodeProviderMatchArm};
+
+            let arm = ExitCodeProviderMatchArm {
+                crate_root: &crate_root,
+                field_container,
+                pattern_ident: &quote! { Self },
+            };
+            let arm = quote! { #arm };
+
+            let exit_code_impl = ExitCodeProvider {
+                crate_root: &crate_root,
+                arms: &[arm],
+                original_generics,
+                parameterized_error_name: &parameterized_struct_name,
+                where_clauses: &where_clauses,
+            };
+
+            Some(quote! { #exit_code_impl })
+        } else {
+            None
+        };
+
         use crate::shared::ContextSelector;
 
         let selector_doc_string = format!("SNAFU context selector for the `{}` error", name);
@@ -787,6 +1405,7 @@ impl NamedStructInfo {
             visibility: selector_visibility,
             where_clauses: &where_clauses,
             default_suffix: &SuffixKind::Default,
+            with_defaults: false,
         };
 
         let context = match module {
@@ -809,6 +1428,11 @@ impl NamedStructInfo {
             #error_impl
             #error_compat_impl
             #display_impl
+            #clone_impl
+            #partial_eq_impl
+            #catalog_impl
+            #severity_impl
+            #exit_code_impl
             #context
         }
     }
@@ -830,6 +1454,7 @@ impl TupleStructInfo {
 
         let TupleStructInfo {
             crate_root,
+            exposed,
             generics,
             name,
             provides,
@@ -868,6 +1493,30 @@ impl TupleStructInfo {
             }
         };
 
+        let location_fn = quote! {
+            fn location(&self) -> ::core::option::Option<#crate_root::Location> {
+                #crate_root::ErrorCompat::location(&self.0)
+            }
+        };
+
+        let location_trace_fn = quote! {
+            fn location_trace(&self) -> ::core::option::Option<&#crate_root::LocationTrace> {
+                #crate_root::ErrorCompat::location_trace(&self.0)
+            }
+        };
+
+        let display_source_fn = quote! {
+            fn display_includes_source(&self) -> ::core::option::Option<bool> {
+                #crate_root::ErrorCompat::display_includes_source(&self.0)
+            }
+        };
+
+        let deepest_backtrace_fn = quote! {
+            fn deepest_backtrace(&self) -> ::core::option::Option<&#crate_root::Backtrace> {
+                #crate_root::ErrorCompat::deepest_backtrace(&self.0)
+            }
+        };
+
         let provide_fn = if cfg!(feature = "unstable-provider-api") {
             use shared::error::PROVIDE_ARG;
 
@@ -906,6 +1555,10 @@ impl TupleStructInfo {
                 #(#where_clauses),*
             {
                 #backtrace_fn
+                #location_fn
+                #location_trace_fn
+                #display_source_fn
+                #deepest_backtrace_fn
             }
         };
 
@@ -921,6 +1574,31 @@ impl TupleStructInfo {
             }
         };
 
+        let exposed_impl = if exposed.is_empty() {
+            None
+        } else {
+            let methods = exposed.iter().map(|ExposedMethod { name, ty }| {
+                let doc = format!("Forwards to `{}` of the underlying error", name);
+
+                quote! {
+                    #[doc = #doc]
+                    pub fn #name(&self) -> #ty {
+                        self.0.#name()
+                    }
+                }
+            });
+
+            Some(quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#generics> #parameterized_struct_name
+                where
+                    #(#where_clauses),*
+                {
+                    #(#methods)*
+                }
+            })
+        };
+
         let tuple_field = quote! { 0 };
         let source_info = shared::SourceInfo::from_transformation(&tuple_field, &transformation);
         // FUTURE: Should we support implicit fields in opaque / tuple structs?
@@ -932,6 +1610,7 @@ impl TupleStructInfo {
             generics,
             where_clauses: &where_clauses,
             error_constructor_name: &name,
+            construct_default_fields: quote! {},
             construct_implicit_fields_with_source,
         };
 
@@ -939,6 +1618,7 @@ impl TupleStructInfo {
             #error_impl
             #error_compat_impl
             #display_impl
+            #exposed_impl
             #from_impl
         }
     }
@@ -42,12 +42,22 @@ mod kw {
     use syn::custom_keyword;
 
     custom_keyword!(backtrace);
+    custom_keyword!(builder);
+    custom_keyword!(catalog);
+    custom_keyword!(clone);
     custom_keyword!(context);
     custom_keyword!(crate_root);
     custom_keyword!(display);
+    custom_keyword!(display_source);
+    custom_keyword!(display_with);
+    custom_keyword!(exit_code);
+    custom_keyword!(expose);
     custom_keyword!(implicit);
     custom_keyword!(module);
+    custom_keyword!(opaque);
+    custom_keyword!(partial_eq);
     custom_keyword!(provide);
+    custom_keyword!(severity);
     custom_keyword!(source);
     custom_keyword!(transparent);
     custom_keyword!(visibility);
@@ -61,6 +71,8 @@ mod kw {
     custom_keyword!(suffix);
 
     custom_keyword!(opt);
+
+    custom_keyword!(eq);
 }
 
 #[derive(Default)]
@@ -165,16 +177,27 @@ impl DocCommentBuilder {
 
 enum Attribute {
     Backtrace(Backtrace),
+    Catalog(Catalog),
+    Cloneable(Cloneable),
+    ContextBuilder(ContextBuilder),
     ContextFlag(ContextFlag),
     ContextName(ContextName),
     ContextSuffix(ContextSuffix),
     CrateRoot(CrateRoot),
+    DefaultValue(DefaultValue),
     Display(Display),
+    DisplaySource(DisplaySource),
+    DisplayWith(DisplayWith),
     DocComment(DocComment),
+    ExitCode(ExitCode),
+    FromSource(FromSource),
     Implicit(Implicit),
     Module(Module),
+    Opaque(Opaque),
+    PartialEquality(PartialEquality),
     ProvideFlag(ProvideFlag),
     ProvideExpression(ProvideExpression),
+    Severity(Severity),
     SourceFlag(SourceFlag),
     SourceFrom(SourceFrom),
     Transparent(Transparent),
@@ -203,19 +226,30 @@ fn syn_attrs(
             for pair in a.into_pairs() {
                 match pair.into_value() {
                     NestedAttribute::Backtrace(a) => f(Attribute::Backtrace(a)),
+                    NestedAttribute::Catalog(a) => f(Attribute::Catalog(a)),
+                    NestedAttribute::Cloneable(a) => f(Attribute::Cloneable(a)),
                     NestedAttribute::Context(a) => match a {
+                        Context::Builder(a) => f(Attribute::ContextBuilder(a)),
                         Context::Flag(a) => f(Attribute::ContextFlag(a)),
                         Context::Name(a) => f(Attribute::ContextName(a)),
                         Context::Suffix(a) => f(Attribute::ContextSuffix(a)),
                     },
                     NestedAttribute::CrateRoot(a) => f(Attribute::CrateRoot(a)),
+                    NestedAttribute::DefaultValue(a) => f(Attribute::DefaultValue(a)),
                     NestedAttribute::Display(a) => f(Attribute::Display(a)),
+                    NestedAttribute::DisplaySource(a) => f(Attribute::DisplaySource(a)),
+                    NestedAttribute::DisplayWith(a) => f(Attribute::DisplayWith(a)),
+                    NestedAttribute::ExitCode(a) => f(Attribute::ExitCode(a)),
+                    NestedAttribute::FromSource(a) => f(Attribute::FromSource(a)),
                     NestedAttribute::Implicit(a) => f(Attribute::Implicit(a)),
                     NestedAttribute::Module(a) => f(Attribute::Module(a)),
+                    NestedAttribute::Opaque(a) => f(Attribute::Opaque(a)),
+                    NestedAttribute::PartialEquality(a) => f(Attribute::PartialEquality(a)),
                     NestedAttribute::Provide(a) => match a {
                         Provide::Flag(a) => f(Attribute::ProvideFlag(a)),
                         Provide::Expression(a) => f(Attribute::ProvideExpression(a)),
                     },
+                    NestedAttribute::Severity(a) => f(Attribute::Severity(a)),
                     NestedAttribute::Source(a) => a.flatten(|a| match a {
                         Source::Flag(a) => f(Attribute::SourceFlag(a)),
                         Source::From(a) => f(Attribute::SourceFrom(a)),
@@ -356,12 +390,22 @@ where
 
 enum NestedAttribute {
     Backtrace(Backtrace),
+    Catalog(Catalog),
+    Cloneable(Cloneable),
     Context(Context),
     CrateRoot(CrateRoot),
+    DefaultValue(DefaultValue),
     Display(Display),
+    DisplaySource(DisplaySource),
+    DisplayWith(DisplayWith),
+    ExitCode(ExitCode),
+    FromSource(FromSource),
     Implicit(Implicit),
     Module(Module),
+    Opaque(Opaque),
+    PartialEquality(PartialEquality),
     Provide(Provide),
+    Severity(Severity),
     Source(NestedSource),
     Transparent(Transparent),
     Visibility(Visibility),
@@ -373,18 +417,38 @@ impl Parse for NestedAttribute {
         let lookahead = input.lookahead1();
         if lookahead.peek(kw::backtrace) {
             input.parse().map(NestedAttribute::Backtrace)
+        } else if lookahead.peek(kw::catalog) {
+            input.parse().map(NestedAttribute::Catalog)
+        } else if lookahead.peek(kw::clone) {
+            input.parse().map(NestedAttribute::Cloneable)
         } else if lookahead.peek(kw::context) {
             input.parse().map(NestedAttribute::Context)
         } else if lookahead.peek(kw::crate_root) {
             input.parse().map(NestedAttribute::CrateRoot)
+        } else if lookahead.peek(token::Default) {
+            input.parse().map(NestedAttribute::DefaultValue)
+        } else if lookahead.peek(kw::display_source) {
+            input.parse().map(NestedAttribute::DisplaySource)
+        } else if lookahead.peek(kw::display_with) {
+            input.parse().map(NestedAttribute::DisplayWith)
         } else if lookahead.peek(kw::display) {
             input.parse().map(NestedAttribute::Display)
+        } else if lookahead.peek(kw::exit_code) {
+            input.parse().map(NestedAttribute::ExitCode)
+        } else if lookahead.peek(kw::from) {
+            input.parse().map(NestedAttribute::FromSource)
         } else if lookahead.peek(kw::implicit) {
             input.parse().map(NestedAttribute::Implicit)
         } else if lookahead.peek(kw::module) {
             input.parse().map(NestedAttribute::Module)
+        } else if lookahead.peek(kw::opaque) {
+            input.parse().map(NestedAttribute::Opaque)
+        } else if lookahead.peek(kw::partial_eq) {
+            input.parse().map(NestedAttribute::PartialEquality)
         } else if lookahead.peek(kw::provide) {
             input.parse().map(NestedAttribute::Provide)
+        } else if lookahead.peek(kw::severity) {
+            input.parse().map(NestedAttribute::Severity)
         } else if lookahead.peek(kw::source) {
             input.parse().map(NestedAttribute::Source)
         } else if lookahead.peek(kw::transparent) {
@@ -420,7 +484,102 @@ impl ToTokens for Backtrace {
     }
 }
 
+struct Cloneable {
+    clone_token: kw::clone,
+}
+
+impl Parse for Cloneable {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            clone_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Cloneable {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.clone_token.to_tokens(tokens);
+    }
+}
+
+struct Catalog {
+    catalog_token: kw::catalog,
+}
+
+impl Parse for Catalog {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            catalog_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Catalog {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.catalog_token.to_tokens(tokens);
+    }
+}
+
+struct FromSource {
+    from_token: kw::from,
+}
+
+impl Parse for FromSource {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            from_token: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for FromSource {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.from_token.to_tokens(tokens);
+    }
+}
+
+struct PartialEquality {
+    partial_eq_token: kw::partial_eq,
+    eq: Option<(token::Paren, kw::eq)>,
+}
+
+impl PartialEquality {
+    fn is_eq(&self) -> bool {
+        self.eq.is_some()
+    }
+}
+
+impl Parse for PartialEquality {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let partial_eq_token = input.parse()?;
+        let eq = if input.peek(token::Paren) {
+            let content;
+            let paren_token = parenthesized!(content in input);
+            Some((paren_token, content.parse()?))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            partial_eq_token,
+            eq,
+        })
+    }
+}
+
+impl ToTokens for PartialEquality {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.partial_eq_token.to_tokens(tokens);
+        if let Some((paren_token, eq_token)) = &self.eq {
+            paren_token.surround(tokens, |tokens| {
+                eq_token.to_tokens(tokens);
+            });
+        }
+    }
+}
+
 enum Context {
+    Builder(ContextBuilder),
     Flag(ContextFlag),
     Name(ContextName),
     Suffix(ContextSuffix),
@@ -441,6 +600,12 @@ impl Parse for Context {
                 paren_token,
                 content,
             } => match content {
+                ContextArg::Builder { builder_token } => Context::Builder(ContextBuilder {
+                    context_token,
+                    paren_token,
+                    builder_token,
+                }),
+
                 ContextArg::Flag { value } => Context::Flag(ContextFlag {
                     context_token,
                     arg: MaybeArg::Some {
@@ -477,6 +642,21 @@ impl Parse for Context {
     }
 }
 
+struct ContextBuilder {
+    context_token: kw::context,
+    paren_token: token::Paren,
+    builder_token: kw::builder,
+}
+
+impl ToTokens for ContextBuilder {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.context_token.to_tokens(tokens);
+        self.paren_token.surround(tokens, |tokens| {
+            self.builder_token.to_tokens(tokens);
+        });
+    }
+}
+
 struct ContextFlag {
     context_token: kw::context,
     arg: MaybeArg<LitBool>,
@@ -530,6 +710,9 @@ impl ToTokens for ContextSuffix {
 }
 
 enum ContextArg {
+    Builder {
+        builder_token: kw::builder,
+    },
     Flag {
         value: LitBool,
     },
@@ -552,6 +735,10 @@ impl Parse for ContextArg {
             Ok(ContextArg::Flag {
                 value: input.parse()?,
             })
+        } else if lookahead.peek(kw::builder) {
+            Ok(ContextArg::Builder {
+                builder_token: input.parse()?,
+            })
         } else if lookahead.peek(kw::suffix) {
             let content;
             Ok(ContextArg::Suffix {
@@ -655,6 +842,46 @@ impl ToTokens for CrateRoot {
     }
 }
 
+struct DefaultValue {
+    default_token: token::Default,
+    value: Option<(token::Eq, Expr)>,
+}
+
+impl DefaultValue {
+    fn into_expr(self) -> Expr {
+        match self.value {
+            Some((_, expr)) => expr,
+            None => syn::parse_quote! { ::core::default::Default::default() },
+        }
+    }
+}
+
+impl Parse for DefaultValue {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let default_token = input.parse()?;
+        let value = if input.peek(token::Eq) {
+            Some((input.parse()?, input.parse()?))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            default_token,
+            value,
+        })
+    }
+}
+
+impl ToTokens for DefaultValue {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.default_token.to_tokens(tokens);
+        if let Some((eq_token, expr)) = &self.value {
+            eq_token.to_tokens(tokens);
+            expr.to_tokens(tokens);
+        }
+    }
+}
+
 struct Display {
     display_token: kw::display,
     paren_token: token::Paren,
@@ -663,18 +890,26 @@ struct Display {
 
 impl Display {
     fn into_display(self) -> crate::Display {
-        let exprs: Vec<_> = self.args.into_iter().collect();
+        let mut exprs: Vec<_> = self.args.into_iter().collect();
+        let key = extract_display_key(&mut exprs);
         let mut shorthand_names = BTreeSet::new();
         let mut assigned_names = BTreeSet::new();
+        let mut placeholders = Vec::new();
+        let mut placeholder_format = None;
 
         // Do a best-effort parsing here; if we fail, the compiler
         // will likely spit out something more useful when it tries to
         // parse it.
         if let Some((Expr::Lit(l), args)) = exprs.split_first() {
             if let Lit::Str(s) = &l.lit {
-                let format_str = s.value();
+                let (format_str, p) = extract_display_with(&s.value(), s.span());
                 let names = extract_field_names(&format_str).map(|n| format_ident!("{}", n));
                 shorthand_names.extend(names);
+
+                if !p.is_empty() {
+                    placeholder_format = Some(LitStr::new(&format_str, s.span()));
+                    placeholders = p;
+                }
             }
 
             for arg in args {
@@ -688,12 +923,94 @@ impl Display {
 
         crate::Display {
             exprs,
+            key,
             shorthand_names,
             assigned_names,
+            placeholder_format,
+            placeholders,
         }
     }
 }
 
+/// Replaces each `{field:with(function)}` in a format string with a
+/// unique named argument that displays the field using the function.
+fn extract_display_with(
+    mut s: &str,
+    span: proc_macro2::Span,
+) -> (String, Vec<crate::DisplayWithPlaceholder>) {
+    let mut format_str = String::with_capacity(s.len());
+    let mut placeholders = Vec::new();
+
+    while let Some(open_curly) = s.find('{') {
+        let (before, after) = s.split_at(open_curly + '{'.len_utf8());
+        format_str.push_str(before);
+        s = after;
+
+        if s.starts_with('{') {
+            format_str.push('{');
+            s = &s['{'.len_utf8()..];
+            continue;
+        }
+
+        let end_curly = match s.find('}') {
+            Some(idx) => idx,
+            None => break,
+        };
+        let format_contents = &s[..end_curly];
+
+        let function = format_contents.split_once(':').and_then(|(name, spec)| {
+            let function = spec.strip_prefix("with(")?.strip_suffix(')')?;
+            let function = syn::parse_str::<syn::Path>(function).ok()?;
+            let field = syn::parse_str::<Ident>(name).ok()?;
+            Some((field, function))
+        });
+
+        if let Some((field, function)) = function {
+            let placeholder = format_ident!("__snafu_display_with_{}", placeholders.len());
+            format_str.push_str(&placeholder.to_string());
+
+            let function = function
+                .into_token_stream()
+                .into_iter()
+                .map(|mut tt| {
+                    tt.set_span(span);
+                    tt
+                })
+                .collect();
+
+            placeholders.push(crate::DisplayWithPlaceholder {
+                placeholder,
+                field,
+                function,
+            });
+
+            s = &s[end_curly..];
+        }
+    }
+
+    format_str.push_str(s);
+    (format_str, placeholders)
+}
+
+/// Removes a leading `key = "..."` argument, which names the message
+/// in a localization catalog. A format string always comes first, so
+/// this cannot be confused with a named format argument.
+fn extract_display_key(exprs: &mut Vec<Expr>) -> Option<LitStr> {
+    let key = match exprs.first() {
+        Some(Expr::Assign(a)) => match (&*a.left, &*a.right) {
+            (Expr::Path(p), Expr::Lit(l)) if p.path.is_ident("key") => match &l.lit {
+                Lit::Str(s) => s.clone(),
+                _ => return None,
+            },
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    exprs.remove(0);
+    Some(key)
+}
+
 pub(crate) fn extract_field_names(mut s: &str) -> impl Iterator<Item = &str> {
     std::iter::from_fn(move || loop {
         let open_curly = s.find('{')?;
@@ -740,6 +1057,113 @@ impl ToTokens for Display {
     }
 }
 
+struct DisplaySource {
+    display_source_token: kw::display_source,
+    paren_token: token::Paren,
+    policy: Ident,
+}
+
+impl DisplaySource {
+    fn into_policy(self) -> crate::DisplaySourcePolicy {
+        if self.policy == "append" {
+            crate::DisplaySourcePolicy::Append
+        } else {
+            crate::DisplaySourcePolicy::Never
+        }
+    }
+}
+
+impl Parse for DisplaySource {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        let display_source_token = input.parse()?;
+        let paren_token = parenthesized!(content in input);
+        let policy: Ident = content.parse()?;
+
+        if policy != "append" && policy != "never" {
+            return Err(syn::Error::new_spanned(
+                policy,
+                "expected one of `append` or `never`",
+            ));
+        }
+
+        Ok(Self {
+            display_source_token,
+            paren_token,
+            policy,
+        })
+    }
+}
+
+impl ToTokens for DisplaySource {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.display_source_token.to_tokens(tokens);
+        self.paren_token.surround(tokens, |tokens| {
+            self.policy.to_tokens(tokens);
+        });
+    }
+}
+
+struct DisplayWith {
+    display_with_token: kw::display_with,
+    eq_token: token::Eq,
+    value: Expr,
+}
+
+impl DisplayWith {
+    fn into_value(self) -> Expr {
+        self.value
+    }
+}
+
+impl Parse for DisplayWith {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            display_with_token: input.parse()?,
+            eq_token: input.parse()?,
+            value: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for DisplayWith {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.display_with_token.to_tokens(tokens);
+        self.eq_token.to_tokens(tokens);
+        self.value.to_tokens(tokens);
+    }
+}
+
+struct ExitCode {
+    exit_code_token: kw::exit_code,
+    eq_token: token::Eq,
+    value: Expr,
+}
+
+impl ExitCode {
+    fn into_value(self) -> Expr {
+        self.value
+    }
+}
+
+impl Parse for ExitCode {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            exit_code_token: input.parse()?,
+            eq_token: input.parse()?,
+            value: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for ExitCode {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.exit_code_token.to_tokens(tokens);
+        self.eq_token.to_tokens(tokens);
+        self.value.to_tokens(tokens);
+    }
+}
+
 struct DocComment {
     _doc_ident: Ident,
     _eq_token: token::Eq,
@@ -807,6 +1231,86 @@ impl ToTokens for Module {
     }
 }
 
+struct Opaque {
+    opaque_token: kw::opaque,
+    paren_token: token::Paren,
+    expose_token: kw::expose,
+    expose_paren_token: token::Paren,
+    methods: Punctuated<ExposedMethod, token::Comma>,
+}
+
+impl Opaque {
+    fn into_methods(self) -> impl Iterator<Item = crate::ExposedMethod> {
+        self.methods.into_iter().map(|m| crate::ExposedMethod {
+            name: m.name,
+            ty: m.ty,
+        })
+    }
+}
+
+impl Parse for Opaque {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        let expose_content;
+
+        Ok(Self {
+            opaque_token: input.parse()?,
+            paren_token: parenthesized!(content in input),
+            expose_token: content.parse()?,
+            expose_paren_token: parenthesized!(expose_content in content),
+            methods: Punctuated::parse_terminated(&expose_content)?,
+        })
+    }
+}
+
+impl ToTokens for Opaque {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.opaque_token.to_tokens(tokens);
+        self.paren_token.surround(tokens, |tokens| {
+            self.expose_token.to_tokens(tokens);
+            self.expose_paren_token.surround(tokens, |tokens| {
+                self.methods.to_tokens(tokens);
+            });
+        });
+    }
+}
+
+struct ExposedMethod {
+    name: Ident,
+    arrow_token: token::RArrow,
+    ty: Type,
+}
+
+impl Parse for ExposedMethod {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name: Ident = input.parse()?;
+
+        if !input.peek(token::RArrow) {
+            return Err(syn::Error::new_spanned(
+                &name,
+                format_args!(
+                    "The return type of `{0}` must be given, such as `{0} -> Type`",
+                    name,
+                ),
+            ));
+        }
+
+        Ok(Self {
+            name,
+            arrow_token: input.parse()?,
+            ty: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for ExposedMethod {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.name.to_tokens(tokens);
+        self.arrow_token.to_tokens(tokens);
+        self.ty.to_tokens(tokens);
+    }
+}
+
 enum Provide {
     Flag(ProvideFlag),
 
@@ -1256,6 +1760,51 @@ impl ToTokens for Transparent {
     }
 }
 
+struct Severity {
+    severity_token: kw::severity,
+    paren_token: token::Paren,
+    level: Ident,
+}
+
+impl Severity {
+    const LEVELS: &'static [&'static str] = &["Warning", "Error", "Fatal"];
+
+    fn into_level(self) -> Ident {
+        self.level
+    }
+}
+
+impl Parse for Severity {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        let severity_token = input.parse()?;
+        let paren_token = parenthesized!(content in input);
+        let level: Ident = content.parse()?;
+
+        if !Self::LEVELS.iter().any(|l| level == l) {
+            return Err(syn::Error::new_spanned(
+                level,
+                "expected one of `Warning`, `Error`, or `Fatal`",
+            ));
+        }
+
+        Ok(Self {
+            severity_token,
+            paren_token,
+            level,
+        })
+    }
+}
+
+impl ToTokens for Severity {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.severity_token.to_tokens(tokens);
+        self.paren_token.surround(tokens, |tokens| {
+            self.level.to_tokens(tokens);
+        });
+    }
+}
+
 struct Visibility {
     visibility_token: kw::visibility,
     visibility: MaybeArg<syn::Visibility>,
@@ -1396,15 +1945,26 @@ macro_rules! def_attributes {
 
 def_attributes![
     Backtrace,
+    Catalog,
+    Cloneable,
+    ContextBuilder,
     ContextFlag,
     ContextName,
     ContextSuffix,
     CrateRoot,
+    DefaultValue,
     Display,
+    DisplaySource,
+    DisplayWith,
+    ExitCode,
+    FromSource,
     Implicit,
     Module,
+    Opaque,
+    PartialEquality,
     ProvideExpression,
     ProvideFlag,
+    Severity,
     SourceFlag,
     SourceFrom,
     Transparent,
@@ -33,6 +33,7 @@ const VALID_C: &str = "an enum, enum variants, or a struct with named fields";
 const VALID_D: &str = "enum variant or struct fields with a name";
 const VALID_E: &str = "enum variants or structs with named fields";
 const VALID_F: &str = "enum variants, structs with named fields, or tuple structs";
+const VALID_G: &str = "tuple structs";
 
 macro_rules! def_attributes {
     ($(($name:ident, $attr:expr, $valid:expr)),*$(,)?) => {
@@ -49,15 +50,26 @@ macro_rules! def_attributes {
 
 def_attributes![
     (Backtrace, "backtrace", VALID_D),
+    (Catalog, "catalog", VALID_B),
+    (Cloneable, "clone", VALID_B),
+    (ContextBuilder, "context(builder)", VALID_E),
     (ContextFlag, "context(bool)", VALID_E),
     (ContextName, "context(name)", VALID_E),
     (ContextSuffix, "context(suffix)", VALID_E),
     (CrateRoot, "crate_root", VALID_A),
+    (DefaultValue, "default", VALID_D),
     (Display, "display", VALID_E),
+    (DisplaySource, "display_source", VALID_B),
+    (DisplayWith, "display_with", VALID_D),
+    (ExitCode, "exit_code", VALID_E),
+    (FromSource, "from", VALID_E),
     (Implicit, "implicit", VALID_D),
     (Module, "module", VALID_B),
+    (Opaque, "opaque", VALID_G),
+    (PartialEquality, "partial_eq", VALID_B),
     (ProvideExpression, "provide(type => expression)", VALID_F),
     (ProvideFlag, "provide(bool)", VALID_D),
+    (Severity, "severity", VALID_C),
     (SourceFlag, "source(bool)", VALID_D),
     (SourceFrom, "source(from)", VALID_D),
     (Transparent, "transparent", VALID_E),
@@ -93,6 +105,26 @@ impl Backtrace {
     pub(super) const DUPLICATE_FIELD: DuplicateField = DuplicateField { field: Self::NAME };
 }
 
+impl ContextBuilder {
+    pub(super) const WITHOUT_CONTEXT_SELECTOR: &'static str =
+        "`context(builder)` attribute requires a context selector and cannot be combined with `context(false)`, `transparent`, or `whatever`";
+}
+
+impl DefaultValue {
+    pub(super) const ON_NON_CONTEXT_FIELD: &'static str =
+        "`default` attribute is only valid on context fields, not on source, backtrace, or implicit fields";
+}
+
+impl DisplaySource {
+    pub(super) const NEVER_WITH_SOURCE: &'static str =
+        "`display_source(never)` forbids using the source error in the display format; `Report` will show it instead";
+}
+
+impl DisplayWith {
+    pub(super) const ON_SOURCE_FIELD: &'static str =
+        "`display_with` attribute is not valid on source fields; the source error is displayed by its own `Display` implementation";
+}
+
 impl Implicit {
     pub(super) const FALSE_DOES_NOTHING: DoesNothing = DoesNothing {
         attribute: "implicit(false)",
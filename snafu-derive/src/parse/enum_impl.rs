@@ -1,17 +1,25 @@
+use proc_macro2::Span;
+
 use crate::{
     parse::{
         self,
         attr::{self, ErrorLocation},
-        into_crate_root, AtMostOne, Attribute, ContextSuffix, CrateRoot, Module, SynErrors,
+        field_container_impl, into_crate_root, AtMostOne, Attribute, Catalog, Cloneable,
+        ContextSuffix, CrateRoot, DisplaySource, Module, PartialEquality, Severity, SynErrors,
         Visibility,
     },
     EnumInfo,
 };
 
 struct Attributes {
+    catalog: Option<Catalog>,
+    cloneable: Option<Cloneable>,
     context_suffix: Option<ContextSuffix>,
     crate_root: Option<CrateRoot>,
+    display_source: Option<DisplaySource>,
     module: Option<Module>,
+    partial_eq: Option<PartialEquality>,
+    severity: Option<Severity>,
     visibility: Option<Visibility>,
 }
 
@@ -20,9 +28,14 @@ impl Attributes {
         let location = ErrorLocation::OnEnum;
         let mut errors = SynErrors::default();
 
+        let mut catalogs = AtMostOne::attribute(attr::Catalog, location);
+        let mut cloneables = AtMostOne::attribute(attr::Cloneable, location);
         let mut context_suffixes = AtMostOne::attribute(attr::ContextSuffix, location);
         let mut crate_roots = AtMostOne::attribute(attr::CrateRoot, location);
+        let mut display_sources = AtMostOne::attribute(attr::DisplaySource, location);
         let mut modules = AtMostOne::attribute(attr::Module, location);
+        let mut partial_eqs = AtMostOne::attribute(attr::PartialEquality, location);
+        let mut severities = AtMostOne::attribute(attr::Severity, location);
         let mut visibilities = AtMostOne::attribute(attr::Visibility, location);
 
         parse::syn_attrs(attrs, &mut errors, |errors, attr| {
@@ -30,16 +43,27 @@ impl Attributes {
 
             match attr {
                 Backtrace(a) => errors.push_invalid_flag(a, location),
+                Catalog(a) => catalogs.push(a),
+                Cloneable(a) => cloneables.push(a),
+                ContextBuilder(a) => errors.push_invalid(a, location),
                 ContextFlag(a) => errors.push_invalid_flag(a, location),
                 ContextName(a) => errors.push_invalid(a, location),
                 ContextSuffix(a) => context_suffixes.push(a),
                 CrateRoot(a) => crate_roots.push(a),
+                DefaultValue(a) => errors.push_invalid(a, location),
                 Display(a) => errors.push_invalid(a, location),
+                DisplaySource(a) => display_sources.push(a),
+                DisplayWith(a) => errors.push_invalid(a, location),
                 DocComment(_a) => { /* no-op */ }
+                ExitCode(a) => errors.push_invalid(a, location),
+                FromSource(a) => errors.push_invalid(a, location),
                 Implicit(a) => errors.push_invalid_flag(a, location),
                 Module(a) => modules.push(a),
+                Opaque(a) => errors.push_invalid(a, location),
+                PartialEquality(a) => partial_eqs.push(a),
                 ProvideFlag(a) => errors.push_invalid_flag(a, location),
                 ProvideExpression(a) => errors.push_invalid(a, location),
+                Severity(a) => severities.push(a),
                 SourceFlag(a) => errors.push_invalid_flag(a, location),
                 SourceFrom(a) => errors.push_invalid(a, location),
                 Transparent(a) => errors.push_invalid_flag(a, location),
@@ -48,15 +72,25 @@ impl Attributes {
             }
         });
 
+        let catalog = catalogs.finish_default(&mut errors);
+        let cloneable = cloneables.finish_default(&mut errors);
         let context_suffix = context_suffixes.finish_default(&mut errors);
         let crate_root = crate_roots.finish_default(&mut errors);
+        let display_source = display_sources.finish_default(&mut errors);
         let module = modules.finish_default(&mut errors);
+        let partial_eq = partial_eqs.finish_default(&mut errors);
+        let severity = severities.finish_default(&mut errors);
         let visibility = visibilities.finish_default(&mut errors);
 
         errors.finish(Self {
+            catalog,
+            cloneable,
             context_suffix,
             crate_root,
+            display_source,
             module,
+            partial_eq,
+            severity,
             visibility,
         })
     }
@@ -90,9 +124,14 @@ pub(crate) fn parse_enum(
     };
 
     let Attributes {
+        catalog,
+        cloneable,
         context_suffix,
         crate_root,
+        display_source,
         module,
+        partial_eq,
+        severity,
         visibility,
     } = attrs;
 
@@ -101,14 +140,51 @@ pub(crate) fn parse_enum(
         context_suffix.map_or_else(Default::default, |cs| cs.suffix.into_suffix_kind());
     let default_visibility = visibility.map(|v| v.into_arbitrary());
     let generics = generics.clone();
+    let has_catalog = catalog.is_some();
+    let is_cloneable = cloneable.is_some();
+    let is_partial_eq = partial_eq.is_some();
+    let is_eq = partial_eq.as_ref().map_or(false, PartialEquality::is_eq);
     let module = module.map(|m| m.into_value());
     let name = name.clone();
 
+    let default_severity = severity.map(|s| s.into_level());
+    let has_severity = default_severity.is_some() || variants.iter().any(|v| v.severity.is_some());
+
+    if is_cloneable {
+        for variant in &mut variants {
+            field_container_impl::share_sources(variant, &crate_root);
+        }
+    }
+
+    if has_severity {
+        let default_severity =
+            default_severity.unwrap_or_else(|| syn::Ident::new("Error", Span::call_site()));
+
+        for variant in &mut variants {
+            variant
+                .severity
+                .get_or_insert_with(|| default_severity.clone());
+            field_container_impl::provide_severity(variant, &crate_root);
+        }
+    }
+
+    if let Some(display_source) = display_source.map(|d| d.into_policy()) {
+        for variant in &mut variants {
+            variant.display_source = Some(display_source);
+            field_container_impl::check_display_source(variant, &mut errors);
+        }
+    }
+
     errors.finish(EnumInfo {
         crate_root,
         default_suffix,
         default_visibility,
         generics,
+        has_catalog,
+        has_severity,
+        is_cloneable,
+        is_eq,
+        is_partial_eq,
         module,
         name,
         variants,
@@ -5,10 +5,12 @@ use crate::{
         self,
         attr::{self, Attribute as _, ErrorForLocation as _, ErrorLocation},
         field_impl::{parse_field, FieldKind},
-        AtMostOne, Attribute, CrateRoot, Display, DocCommentBuilder, FlagAttribute as _, Module,
-        ProvideExpression, Sidecar, SynErrors, Visibility,
+        AtMostOne, Attribute, Catalog, Cloneable, CrateRoot, Display, DisplaySource,
+        DocCommentBuilder, ExitCode, FlagAttribute as _, FromSource, Module, PartialEquality,
+        ProvideExpression, Severity, Sidecar, SynErrors, Visibility,
     },
-    ContextSelectorKind, ContextSelectorName, DocComment, Field, FieldContainer,
+    ContextSelectorKind, ContextSelectorName, DisplaySourcePolicy, DocComment, Field,
+    FieldContainer, Provide, Transformation,
 };
 
 const IMPLICIT_MESSAGE_FIELD_NAME: &str = "message";
@@ -18,11 +20,18 @@ fn is_implicit_message(name: &proc_macro2::Ident) -> bool {
 }
 
 pub struct Attributes {
+    catalog: Option<Catalog>,
+    cloneable: Option<Cloneable>,
     display: Option<Display>,
+    display_source: Option<DisplaySource>,
     doc_comment: Option<DocComment>,
+    exit_code: Option<ExitCode>,
+    from_source: Option<FromSource>,
     module: Option<Module>,
+    partial_eq: Option<PartialEquality>,
     provide_expressions: Vec<ProvideExpression>,
     selector_kind: IntermediateSelectorKind,
+    severity: Option<Severity>,
     visibility: Option<Visibility>,
 }
 
@@ -33,13 +42,21 @@ impl Attributes {
         errors: &mut SynErrors,
         mut f: impl FnMut(&mut SynErrors, CrateRoot),
     ) -> Self {
+        let mut catalogs = AtMostOne::attribute(attr::Catalog, location);
+        let mut cloneables = AtMostOne::attribute(attr::Cloneable, location);
+        let mut context_builders = AtMostOne::attribute(attr::ContextBuilder, location);
         let mut context_flags = AtMostOne::attribute(attr::ContextFlag, location);
         let mut context_names = AtMostOne::attribute(attr::ContextName, location);
         let mut context_suffixes = AtMostOne::attribute(attr::ContextSuffix, location);
         let mut displays = AtMostOne::attribute(attr::Display, location);
+        let mut display_sources = AtMostOne::attribute(attr::DisplaySource, location);
         let mut doc_comment = DocCommentBuilder::default();
+        let mut exit_codes = AtMostOne::attribute(attr::ExitCode, location);
+        let mut from_sources = AtMostOne::attribute(attr::FromSource, location);
         let mut modules = AtMostOne::attribute(attr::Module, location);
+        let mut partial_eqs = AtMostOne::attribute(attr::PartialEquality, location);
         let mut provide_expressions = Vec::new();
+        let mut severities = AtMostOne::attribute(attr::Severity, location);
         let mut transparents = AtMostOne::attribute(attr::Transparent, location);
         let mut visibilities = AtMostOne::attribute(attr::Visibility, location);
         let mut whatevers = AtMostOne::attribute(attr::Whatever, location);
@@ -49,16 +66,27 @@ impl Attributes {
 
             match attr {
                 Backtrace(a) => errors.push_invalid_flag(a, location),
+                Catalog(a) => catalogs.push(a),
+                Cloneable(a) => cloneables.push(a),
+                ContextBuilder(a) => context_builders.push(a),
                 ContextFlag(a) => context_flags.push(a),
                 ContextName(a) => context_names.push(a),
                 ContextSuffix(a) => context_suffixes.push(a),
                 CrateRoot(a) => f(errors, a),
+                DefaultValue(a) => errors.push_invalid(a, location),
                 Display(a) => displays.push(a),
+                DisplaySource(a) => display_sources.push(a),
+                DisplayWith(a) => errors.push_invalid(a, location),
                 DocComment(a) => doc_comment.push(&a.str.value()),
+                ExitCode(a) => exit_codes.push(a),
+                FromSource(a) => from_sources.push(a),
                 Implicit(a) => errors.push_invalid_flag(a, location),
                 Module(a) => modules.push(a),
+                Opaque(a) => errors.push_invalid(a, location),
+                PartialEquality(a) => partial_eqs.push(a),
                 ProvideFlag(a) => errors.push_invalid_flag(a, location),
                 ProvideExpression(a) => provide_expressions.push(a),
+                Severity(a) => severities.push(a),
                 SourceFlag(a) => errors.push_invalid_flag(a, location),
                 SourceFrom(a) => errors.push_invalid(a, location),
                 Transparent(a) => transparents.push(a),
@@ -67,12 +95,20 @@ impl Attributes {
             }
         });
 
+        let catalog = catalogs.finish_default(errors);
+        let cloneable = cloneables.finish_default(errors);
+        let context_builder = context_builders.finish_default(errors);
         let context_flag = context_flags.finish_default(errors);
         let context_name = context_names.finish_default(errors);
         let context_suffix = context_suffixes.finish_default(errors);
         let display = displays.finish_default(errors);
+        let display_source = display_sources.finish_default(errors);
         let doc_comment = doc_comment.finish();
+        let exit_code = exit_codes.finish_default(errors);
+        let from_source = from_sources.finish_default(errors);
         let module = modules.finish_default(errors);
+        let partial_eq = partial_eqs.finish_default(errors);
+        let severity = severities.finish_default(errors);
         let transparent = transparents.finish_default(errors);
         let visibility = visibilities.finish_default(errors);
         let whatever = whatevers.finish_default(errors);
@@ -118,10 +154,12 @@ impl Attributes {
 
             (None, Some(cn), None, None, None) => IntermediateSelectorKind::WithContext {
                 selector_name: ContextSelectorName::Provided(cn.name),
+                is_builder: false,
             },
 
             (None, None, Some(cs), None, None) => IntermediateSelectorKind::WithContext {
                 selector_name: ContextSelectorName::Suffixed(cs.suffix.into_suffix_kind()),
+                is_builder: false,
             },
 
             (None, None, None, Some(_t), None) => IntermediateSelectorKind::WithoutContext {
@@ -156,17 +194,76 @@ impl Attributes {
             }
         };
 
+        let selector_kind = match (context_builder, selector_kind) {
+            (Some(_), IntermediateSelectorKind::WithContext { selector_name, .. }) => {
+                IntermediateSelectorKind::WithContext {
+                    selector_name,
+                    is_builder: true,
+                }
+            }
+
+            (Some(cb), selector_kind) => {
+                errors.push_new(cb, attr::ContextBuilder::WITHOUT_CONTEXT_SELECTOR);
+                selector_kind
+            }
+
+            (None, selector_kind) => selector_kind,
+        };
+
         Self {
+            catalog,
+            cloneable,
             display,
+            display_source,
             doc_comment,
+            exit_code,
+            from_source,
             module,
+            partial_eq,
             provide_expressions,
             selector_kind,
+            severity,
             visibility,
         }
     }
 }
 
+impl Attributes {
+    pub(super) fn has_catalog(&self) -> bool {
+        self.catalog.is_some()
+    }
+
+    pub(super) fn take_catalog(&mut self) -> Option<Catalog> {
+        self.catalog.take()
+    }
+
+    pub(super) fn is_cloneable(&self) -> bool {
+        self.cloneable.is_some()
+    }
+
+    pub(super) fn take_cloneable(&mut self) -> Option<Cloneable> {
+        self.cloneable.take()
+    }
+
+    pub(super) fn take_display_source(&mut self) -> Option<DisplaySource> {
+        self.display_source.take()
+    }
+
+    pub(super) fn is_partial_eq(&self) -> bool {
+        self.partial_eq.is_some()
+    }
+
+    pub(super) fn is_eq(&self) -> bool {
+        self.partial_eq
+            .as_ref()
+            .map_or(false, PartialEquality::is_eq)
+    }
+
+    pub(super) fn take_partial_eq(&mut self) -> Option<PartialEquality> {
+        self.partial_eq.take()
+    }
+}
+
 pub(super) fn parse_field_container(
     name: &syn::Ident,
     variant_span: impl quote::ToTokens,
@@ -175,11 +272,18 @@ pub(super) fn parse_field_container(
     inner_location: ErrorLocation,
 ) -> syn::Result<FieldContainer> {
     let Attributes {
+        catalog: _,
+        cloneable: _,
         display,
+        display_source,
         doc_comment,
+        exit_code,
+        from_source,
         module,
+        partial_eq: _,
         provide_expressions,
         selector_kind,
+        severity,
         visibility,
     } = attrs;
 
@@ -233,13 +337,40 @@ pub(super) fn parse_field_container(
     let is_transparent = selector_kind.is_transparent();
 
     let selector_kind = match selector_kind {
-        IntermediateSelectorKind::WithContext { selector_name } => ContextSelectorKind::Context {
+        IntermediateSelectorKind::WithContext {
             selector_name,
-            source_field,
-            user_fields,
-        },
+            is_builder,
+        } => {
+            if let Some(from_source) = &from_source {
+                if source_field.is_none() {
+                    errors.push_new(from_source, "`from` requires a source field");
+                }
+
+                for Field { original, .. } in user_fields.iter().filter(|f| f.default.is_none()) {
+                    errors.push_new(
+                        original,
+                        "Context fields must have a default value when using `from`",
+                    );
+                }
+            }
+
+            ContextSelectorKind::Context {
+                selector_name,
+                source_field,
+                user_fields,
+                is_builder,
+                from_source: from_source.is_some(),
+            }
+        }
 
         IntermediateSelectorKind::WithoutContext { source } => {
+            if let Some(from_source) = &from_source {
+                errors.push_new(
+                    from_source,
+                    format_args!("{} already implement `From` for the source", source),
+                );
+            }
+
             for Field { original, .. } in user_fields {
                 errors.push_new(
                     original,
@@ -262,6 +393,10 @@ pub(super) fn parse_field_container(
         }
 
         IntermediateSelectorKind::Whatever => {
+            if let Some(from_source) = &from_source {
+                errors.push_new(from_source, "Whatever selectors cannot use `from`");
+            }
+
             let txt = "Whatever selectors must have exactly one message field";
             let mut message_fields = AtMostOne::new(txt);
 
@@ -291,37 +426,175 @@ pub(super) fn parse_field_container(
     };
 
     let display_format = display.map(|d| d.into_display());
+    let display_source = display_source.map(|d| d.into_policy());
+    let exit_code = exit_code.map(|e| e.into_value());
     let module = module.map(|m| m.into_value());
     let name = name.clone();
     let provides = provide_expressions
         .into_iter()
         .map(|p| p.into_provide())
         .collect();
+    let severity = severity.map(|s| s.into_level());
     let visibility = visibility.map(|v| v.into_arbitrary());
 
-    errors.finish(FieldContainer {
+    let field_container = FieldContainer {
         backtrace_field,
         display_format,
+        display_source,
         doc_comment,
+        exit_code,
         implicit_fields,
         is_transparent,
         module,
         name,
         provides,
         selector_kind,
+        severity,
         visibility,
-    })
+    };
+
+    check_display_source(&field_container, &mut errors);
+
+    errors.finish(field_container)
+}
+
+/// Ensures that the display format does not mention the source field
+/// when the source is never supposed to be displayed.
+pub(super) fn check_display_source(field_container: &FieldContainer, errors: &mut SynErrors) {
+    if field_container.display_source != Some(DisplaySourcePolicy::Never) {
+        return;
+    }
+
+    let source_name = match field_container.selector_kind.source_field() {
+        Some(source_field) => source_field.name(),
+        None => return,
+    };
+
+    for usage in field_container.display_uses(source_name) {
+        errors.push_new(usage, attr::DisplaySource::NEVER_WITH_SOURCE);
+    }
+}
+
+/// Makes the severity available through the provider API.
+pub(super) fn provide_severity(
+    field_container: &mut FieldContainer,
+    crate_root: &dyn quote::ToTokens,
+) {
+    if let Some(level) = &field_container.severity {
+        field_container.provides.push(Provide {
+            is_opt: false,
+            is_ref: false,
+            ty: syn::parse_quote! { #crate_root::Severity },
+            expr: syn::parse_quote! { #crate_root::Severity::#level },
+        });
+    }
+}
+
+/// Source fields of a cloneable error that are declared as
+/// `Shared<T>` are constructed from a `T` (or a `Box<T>` for trait
+/// objects) and report the `T` as the error's source.
+pub(super) fn share_sources(
+    field_container: &mut FieldContainer,
+    crate_root: &dyn quote::ToTokens,
+) {
+    let source_field = match &mut field_container.selector_kind {
+        ContextSelectorKind::Context { source_field, .. } => source_field.as_mut(),
+        ContextSelectorKind::NoContext { source_field } => Some(source_field),
+        ContextSelectorKind::Whatever { .. } => None,
+    };
+
+    let source_field = match source_field {
+        Some(f) => f,
+        None => return,
+    };
+
+    let shared_ty = match shared_inner_type(source_field.transformation.target_ty(), crate_root) {
+        Some(ty) => ty.clone(),
+        None => return,
+    };
+
+    source_field.is_shared = true;
+
+    if let Transformation::None {
+        target_ty,
+        from_is_generic: false,
+    } = &source_field.transformation
+    {
+        let (source_ty, expr) = match shared_ty {
+            syn::Type::TraitObject(_) => (
+                syn::parse_quote! { #crate_root::__Box<#shared_ty> },
+                syn::parse_quote! { #crate_root::Shared::from },
+            ),
+            _ => (shared_ty, syn::parse_quote! { #crate_root::Shared::new }),
+        };
+
+        source_field.transformation = Transformation::Transform {
+            source_ty,
+            target_ty: target_ty.clone(),
+            expr,
+        };
+    }
+}
+
+/// Finds `T` when the type is `Shared<T>`, named either directly or
+/// through the SNAFU crate. Other types named `Shared`, such as
+/// `futures::future::Shared`, are left alone.
+fn shared_inner_type<'a>(
+    ty: &'a syn::Type,
+    crate_root: &dyn quote::ToTokens,
+) -> Option<&'a syn::Type> {
+    let path = match ty {
+        syn::Type::Path(p) if p.qself.is_none() => &p.path,
+        _ => return None,
+    };
+
+    let segment = path.segments.last()?;
+    if segment.ident != "Shared" {
+        return None;
+    }
+
+    let prefix: Vec<_> = path.segments.iter().rev().skip(1).collect();
+    let is_snafu_path = match prefix.as_slice() {
+        [] => path.leading_colon.is_none(),
+        [krate] => {
+            let krate = &krate.ident;
+            let leading = path.leading_colon.map(|_| quote::quote! { :: });
+            krate == "snafu"
+                || quote::quote! { #leading #krate }.to_string()
+                    == crate_root.to_token_stream().to_string()
+        }
+        _ => false,
+    };
+    if !is_snafu_path {
+        return None;
+    }
+
+    let args = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(a) if a.args.len() == 1 => &a.args,
+        _ => return None,
+    };
+
+    match args.first()? {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    }
 }
 
 enum IntermediateSelectorKind {
-    WithContext { selector_name: ContextSelectorName },
-    WithoutContext { source: WithoutContextSource },
+    WithContext {
+        selector_name: ContextSelectorName,
+        is_builder: bool,
+    },
+    WithoutContext {
+        source: WithoutContextSource,
+    },
     Whatever,
 }
 
 impl IntermediateSelectorKind {
     const DEFAULT: Self = Self::WithContext {
         selector_name: ContextSelectorName::SUFFIX_DEFAULT,
+        is_builder: false,
     };
 
     fn is_transparent(&self) -> bool {
@@ -2,8 +2,8 @@ use crate::{
     parse::{
         self,
         attr::{self, ErrorForLocation as _, ErrorLocation},
-        into_transformation, AtMostOne, Attribute, Backtrace, FlagAttribute as _, ProvideFlag,
-        Sidecar, SourceFlag, SourceFrom, SynErrors,
+        into_transformation, AtMostOne, Attribute, Backtrace, DefaultValue, DisplayWith,
+        FlagAttribute as _, ProvideFlag, Sidecar, SourceFlag, SourceFrom, SynErrors,
     },
     Field, SourceField,
 };
@@ -25,6 +25,8 @@ fn is_implicit_provide(name: &proc_macro2::Ident) -> bool {
 
 struct Attributes {
     backtrace: Option<Backtrace>,
+    default_value: Option<DefaultValue>,
+    display_with: Option<DisplayWith>,
     implicit: bool,
     provide_flag: Option<ProvideFlag>,
     source_attr_enabled: Option<(bool, SourceOrigin)>,
@@ -37,6 +39,8 @@ impl Attributes {
         let mut errors = SynErrors::default();
 
         let mut backtraces = AtMostOne::attribute(attr::Backtrace, location);
+        let mut default_values = AtMostOne::attribute(attr::DefaultValue, location);
+        let mut display_withs = AtMostOne::attribute(attr::DisplayWith, location);
         let mut implicits = AtMostOne::attribute(attr::Implicit, location);
         let mut provide_flags = AtMostOne::attribute(attr::ProvideFlag, location);
         let mut source_flags = AtMostOne::attribute(attr::SourceFlag, location);
@@ -47,16 +51,27 @@ impl Attributes {
 
             match attr {
                 Backtrace(a) => backtraces.push(a),
+                Catalog(a) => errors.push_invalid(a, location),
+                Cloneable(a) => errors.push_invalid(a, location),
+                ContextBuilder(a) => errors.push_invalid(a, location),
                 ContextFlag(a) => errors.push_invalid_flag(a, location),
                 ContextName(a) => errors.push_invalid(a, location),
                 ContextSuffix(a) => errors.push_invalid(a, location),
                 CrateRoot(a) => errors.push_invalid(a, location),
+                DefaultValue(a) => default_values.push(a),
                 Display(a) => errors.push_invalid(a, location),
+                DisplaySource(a) => errors.push_invalid(a, location),
+                DisplayWith(a) => display_withs.push(a),
                 DocComment(_a) => { /* no-op */ }
+                ExitCode(a) => errors.push_invalid(a, location),
+                FromSource(a) => errors.push_invalid(a, location),
                 Implicit(a) => implicits.push(a),
                 Module(a) => errors.push_invalid(a, location),
+                Opaque(a) => errors.push_invalid(a, location),
+                PartialEquality(a) => errors.push_invalid(a, location),
                 ProvideFlag(a) => provide_flags.push(a),
                 ProvideExpression(a) => errors.push_invalid(a, location),
+                Severity(a) => errors.push_invalid(a, location),
                 SourceFlag(a) => source_flags.push(a),
                 SourceFrom(a) => source_froms.push(a),
                 Transparent(a) => errors.push_invalid_flag(a, location),
@@ -66,6 +81,8 @@ impl Attributes {
         });
 
         let backtrace = backtraces.finish_default(&mut errors);
+        let default_value = default_values.finish_default(&mut errors);
+        let display_with = display_withs.finish_default(&mut errors);
         let implicit = implicits.finish_default(&mut errors);
         let provide_flag = provide_flags.finish_default(&mut errors);
         let source_flag = source_flags.finish_default(&mut errors);
@@ -109,6 +126,8 @@ impl Attributes {
 
         errors.finish(Attributes {
             backtrace,
+            default_value,
+            display_with,
             implicit,
             provide_flag,
             source_attr_enabled,
@@ -129,6 +148,8 @@ pub(super) fn parse_field(syn_field: &syn::Field) -> syn::Result<FieldKind> {
 
     let Attributes {
         backtrace,
+        default_value,
+        display_with,
         implicit,
         provide_flag,
         source_attr_enabled,
@@ -193,10 +214,29 @@ pub(super) fn parse_field(syn_field: &syn::Field) -> syn::Result<FieldKind> {
         }
     };
 
+    let is_context_field = source.is_none() && backtrace.is_none() && !implicit;
+    let default = match default_value {
+        Some(d) if !is_context_field => {
+            errors.push_new(d, attr::DefaultValue::ON_NON_CONTEXT_FIELD);
+            None
+        }
+        d => d.map(DefaultValue::into_expr),
+    };
+
+    let display_with = match display_with {
+        Some(d) if source.is_some() => {
+            errors.push_new(d, attr::DisplayWith::ON_SOURCE_FIELD);
+            None
+        }
+        d => d.map(DisplayWith::into_value),
+    };
+
     let field = Field {
         name: name.clone(),
         ty: syn_field.ty.clone(),
         provide,
+        default,
+        display_with,
         original: syn_field.clone(),
     };
 
@@ -215,6 +255,7 @@ pub(super) fn parse_field(syn_field: &syn::Field) -> syn::Result<FieldKind> {
             transformation,
             backtrace_delegate,
             provide,
+            is_shared: false,
         };
 
         FieldKind::Source(Sidecar(span, field))
@@ -1,7 +1,7 @@
 use crate::{
     parse::{
         attr::{self, ErrorLocation},
-        field_container_impl::{self, parse_field_container},
+        field_container_impl::{self, parse_field_container, provide_severity, share_sources},
         into_crate_root, AtMostOne, CrateRoot, SynErrors,
     },
     NamedStructInfo,
@@ -50,7 +50,12 @@ pub(crate) fn parse_named_struct(
         field_container_attrs,
     } = attrs;
 
-    let field_container = parse_field_container(
+    let has_catalog = field_container_attrs.has_catalog();
+    let is_cloneable = field_container_attrs.is_cloneable();
+    let is_partial_eq = field_container_attrs.is_partial_eq();
+    let is_eq = field_container_attrs.is_eq();
+
+    let mut field_container = parse_field_container(
         name,
         span,
         field_container_attrs,
@@ -61,9 +66,21 @@ pub(crate) fn parse_named_struct(
     let crate_root = into_crate_root(crate_root);
     let generics = generics.clone();
 
+    if is_cloneable {
+        share_sources(&mut field_container, &crate_root);
+    }
+
+    let has_severity = field_container.severity.is_some();
+    provide_severity(&mut field_container, &crate_root);
+
     Ok(NamedStructInfo {
         crate_root,
         field_container,
         generics,
+        has_catalog,
+        has_severity,
+        is_cloneable,
+        is_eq,
+        is_partial_eq,
     })
 }
@@ -12,16 +12,27 @@ impl Attributes {
 
             match attr {
                 Backtrace(a) => errors.push_invalid_flag(a, location),
+                Catalog(a) => errors.push_invalid(a, location),
+                Cloneable(a) => errors.push_invalid(a, location),
+                ContextBuilder(a) => errors.push_invalid(a, location),
                 ContextFlag(a) => errors.push_invalid_flag(a, location),
                 ContextName(a) => errors.push_invalid(a, location),
                 ContextSuffix(a) => errors.push_invalid(a, location),
                 CrateRoot(a) => errors.push_invalid(a, location),
+                DefaultValue(a) => errors.push_invalid(a, location),
                 Display(a) => errors.push_invalid(a, location),
+                DisplaySource(a) => errors.push_invalid(a, location),
+                DisplayWith(a) => errors.push_invalid(a, location),
                 DocComment(_a) => { /* no-op */ }
+                ExitCode(a) => errors.push_invalid(a, location),
+                FromSource(a) => errors.push_invalid(a, location),
                 Implicit(a) => errors.push_invalid_flag(a, location),
                 Module(a) => errors.push_invalid(a, location),
+                Opaque(a) => errors.push_invalid(a, location),
+                PartialEquality(a) => errors.push_invalid(a, location),
                 ProvideFlag(a) => errors.push_invalid_flag(a, location),
                 ProvideExpression(a) => errors.push_invalid(a, location),
+                Severity(a) => errors.push_invalid(a, location),
                 SourceFlag(a) => errors.push_invalid_flag(a, location),
                 SourceFrom(a) => errors.push_invalid(a, location),
                 Transparent(a) => errors.push_invalid_flag(a, location),
@@ -4,13 +4,14 @@ use crate::{
         attr::{self, ErrorLocation},
         into_crate_root, into_transformation,
         tuple_struct_field_impl::parse_tuple_struct_field,
-        AtMostOne, Attribute, CrateRoot, ProvideExpression, SourceFrom, SynErrors,
+        AtMostOne, Attribute, CrateRoot, Opaque, ProvideExpression, SourceFrom, SynErrors,
     },
     TupleStructInfo,
 };
 
 struct Attributes {
     crate_root: Option<CrateRoot>,
+    opaques: Vec<Opaque>,
     provide_expressions: Vec<ProvideExpression>,
     source_from: Option<SourceFrom>,
 }
@@ -21,6 +22,7 @@ impl Attributes {
         let mut errors = SynErrors::default();
 
         let mut crate_roots = AtMostOne::attribute(attr::CrateRoot, location);
+        let mut opaques = Vec::new();
         let mut provide_expressions = Vec::new();
         let mut source_froms = AtMostOne::attribute(attr::SourceFrom, location);
 
@@ -29,16 +31,27 @@ impl Attributes {
 
             match attr {
                 Backtrace(a) => errors.push_invalid_flag(a, location),
+                Catalog(a) => errors.push_invalid(a, location),
+                Cloneable(a) => errors.push_invalid(a, location),
+                ContextBuilder(a) => errors.push_invalid(a, location),
                 ContextFlag(a) => errors.push_invalid_flag(a, location),
                 ContextName(a) => errors.push_invalid(a, location),
                 ContextSuffix(a) => errors.push_invalid(a, location),
                 CrateRoot(a) => crate_roots.push(a),
+                DefaultValue(a) => errors.push_invalid(a, location),
                 Display(a) => errors.push_invalid(a, location),
+                DisplaySource(a) => errors.push_invalid(a, location),
+                DisplayWith(a) => errors.push_invalid(a, location),
                 DocComment(_a) => { /* no-op */ }
+                ExitCode(a) => errors.push_invalid(a, location),
+                FromSource(a) => errors.push_invalid(a, location),
                 Implicit(a) => errors.push_invalid_flag(a, location),
                 Module(a) => errors.push_invalid(a, location),
+                Opaque(a) => opaques.push(a),
+                PartialEquality(a) => errors.push_invalid(a, location),
                 ProvideFlag(a) => errors.push_invalid_flag(a, location),
                 ProvideExpression(a) => provide_expressions.push(a),
+                Severity(a) => errors.push_invalid(a, location),
                 SourceFlag(a) => errors.push_invalid_flag(a, location),
                 SourceFrom(a) => source_froms.push(a),
                 Transparent(a) => errors.push_invalid_flag(a, location),
@@ -52,6 +65,7 @@ impl Attributes {
 
         errors.finish(Self {
             crate_root,
+            opaques,
             provide_expressions,
             source_from,
         })
@@ -72,11 +86,13 @@ pub(crate) fn parse_tuple_struct(
 
     let Attributes {
         crate_root,
+        opaques,
         provide_expressions,
         source_from,
     } = attrs;
 
     let crate_root = into_crate_root(crate_root);
+    let exposed = opaques.into_iter().flat_map(Opaque::into_methods).collect();
     let generics = generics.clone();
     let name = name.clone();
     let provides = provide_expressions
@@ -87,6 +103,7 @@ pub(crate) fn parse_tuple_struct(
 
     Ok(TupleStructInfo {
         crate_root,
+        exposed,
         generics,
         name,
         provides,
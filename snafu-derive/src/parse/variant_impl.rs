@@ -12,7 +12,7 @@ impl Attributes {
         let location = ErrorLocation::OnVariant;
         let mut errors = SynErrors::default();
 
-        let field_container_attrs = field_container_impl::Attributes::from_syn(
+        let mut field_container_attrs = field_container_impl::Attributes::from_syn(
             attrs,
             location,
             &mut errors,
@@ -21,6 +21,22 @@ impl Attributes {
             },
         );
 
+        if let Some(catalog) = field_container_attrs.take_catalog() {
+            errors.push_invalid(catalog, location);
+        }
+
+        if let Some(cloneable) = field_container_attrs.take_cloneable() {
+            errors.push_invalid(cloneable, location);
+        }
+
+        if let Some(display_source) = field_container_attrs.take_display_source() {
+            errors.push_invalid(display_source, location);
+        }
+
+        if let Some(partial_eq) = field_container_attrs.take_partial_eq() {
+            errors.push_invalid(partial_eq, location);
+        }
+
         errors.finish(Self {
             field_container_attrs,
         })
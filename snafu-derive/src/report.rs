@@ -60,7 +60,18 @@ pub fn body(
     let block = quote! {
         {
             #ascribed_original_result;
-            <::snafu::Report<_> as ::core::convert::From<_>>::from(__snafu_body)
+            let __snafu_report = <::snafu::Report<_> as ::core::convert::From<_>>::from(__snafu_body)
+                .__snafu_highlight_crate(::core::module_path!());
+
+            #[allow(unused_imports)]
+            use ::snafu::{
+                __InternalWithErrorCompat as _, __InternalWithExitCode as _,
+                __InternalWithoutErrorCompat as _, __InternalWithoutExitCode as _,
+            };
+            let __snafu_error_compat = ::snafu::__InternalErrorCompat::new(&__snafu_report);
+            let __snafu_report = (&&__snafu_error_compat).__snafu_with_error_compat()(__snafu_report);
+            let __snafu_exit_code = ::snafu::__InternalExitCode::new(&__snafu_report);
+            (&&__snafu_exit_code).__snafu_with_exit_code()(__snafu_report)
         }
     };
 
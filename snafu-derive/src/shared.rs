@@ -1,11 +1,20 @@
 use std::collections::BTreeSet;
 
+pub(crate) use self::catalog::{Catalog, CatalogEntry};
+pub(crate) use self::clone::{Clone, CloneMatchArm};
 pub(crate) use self::context_module::ContextModule;
 pub(crate) use self::context_selector::ContextSelector;
 pub(crate) use self::display::{Display, DisplayMatchArm};
 pub(crate) use self::error::{Error, ErrorProvideMatchArm, ErrorSourceMatchArm};
-pub(crate) use self::error_compat::{ErrorCompat, ErrorCompatBacktraceMatchArm};
+pub(crate) use self::error_compat::{
+    ErrorCompat, ErrorCompatBacktraceMatchArm, ErrorCompatDeepestBacktraceMatchArm,
+    ErrorCompatDisplaySourceMatchArm, ErrorCompatLocationMatchArm,
+    ErrorCompatLocationTraceMatchArm,
+};
+pub(crate) use self::exit_code::{ExitCodeProvider, ExitCodeProviderMatchArm};
 pub(crate) use self::no_context_selector::NoContextSelector;
+pub(crate) use self::partial_eq::{PartialEq, PartialEqMatchArm};
+pub(crate) use self::severity::{Severity, SeverityMatchArm};
 
 pub(crate) struct StaticIdent(&'static str);
 
@@ -222,6 +231,269 @@ pub mod context_module {
     }
 }
 
+pub mod catalog {
+    use super::GenericsWithoutDefaults;
+    use crate::FieldContainer;
+    use proc_macro2::TokenStream;
+    use quote::{quote, ToTokens};
+
+    pub(crate) struct Catalog<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) entries: &'a [TokenStream],
+        pub(crate) original_generics: GenericsWithoutDefaults<'a>,
+        pub(crate) parameterized_error_name: &'a dyn ToTokens,
+        pub(crate) where_clauses: &'a [TokenStream],
+    }
+
+    impl ToTokens for Catalog<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                entries,
+                original_generics,
+                parameterized_error_name,
+                where_clauses,
+            } = *self;
+
+            let catalog_impl = quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#original_generics> #parameterized_error_name
+                where
+                    #(#where_clauses),*
+                {
+                    /// A description of every error this type can represent.
+                    #[allow(dead_code)]
+                    pub const CATALOG: &'static [#crate_root::ErrorDescriptor] = &[
+                        #(#entries),*
+                    ];
+                }
+            };
+
+            stream.extend(catalog_impl);
+        }
+    }
+
+    pub(crate) struct CatalogEntry<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) field_container: &'a FieldContainer,
+        pub(crate) type_name: &'a syn::Ident,
+        pub(crate) variant_name: Option<&'a syn::Ident>,
+    }
+
+    impl ToTokens for CatalogEntry<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                field_container,
+                type_name,
+                variant_name,
+            } = *self;
+
+            let type_name = type_name.to_string();
+            let variant_name = option(variant_name.map(ToString::to_string));
+            let doc = option(field_container.doc_comment.as_ref().map(|d| &d.content));
+
+            let display_format = field_container.display_format.as_ref();
+            let key = option(display_format.and_then(|d| d.key.as_ref()));
+            let display = option(display_template(field_container));
+
+            let fields = field_container
+                .user_fields()
+                .iter()
+                .chain(field_container.selector_kind.message_field())
+                .chain(&field_container.implicit_fields)
+                .chain(&field_container.backtrace_field)
+                .map(|f| {
+                    let name = f.name.to_string();
+                    let ty = written_type(&f.ty);
+                    quote! {
+                        #crate_root::FieldDescriptor {
+                            name: #name,
+                            ty: #ty,
+                        }
+                    }
+                });
+
+            let source = option(
+                field_container
+                    .selector_kind
+                    .source_field()
+                    .map(|f| written_type(f.transformation.target_ty())),
+            );
+
+            let entry = quote! {
+                #crate_root::ErrorDescriptor {
+                    type_name: #type_name,
+                    variant_name: #variant_name,
+                    doc: #doc,
+                    display: #display,
+                    key: #key,
+                    fields: &[#(#fields),*],
+                    source: #source,
+                }
+            };
+
+            stream.extend(entry);
+        }
+    }
+
+    /// The text that `Display` is generated from, mirroring the
+    /// choices made by `DisplayMatchArm`.
+    fn display_template(field_container: &FieldContainer) -> Option<String> {
+        if field_container.is_transparent {
+            return None;
+        }
+
+        let exprs = field_container
+            .display_format
+            .as_ref()
+            .map(|d| &d.exprs[..])
+            .unwrap_or_default();
+
+        let template = match (exprs.first(), &field_container.doc_comment) {
+            (
+                Some(syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(s),
+                    ..
+                })),
+                _,
+            ) => s.value(),
+            (Some(_), _) => quote! { #(#exprs),* }.to_string(),
+            (None, Some(d)) => d.content.clone(),
+            (None, None) => field_container.name.to_string(),
+        };
+
+        Some(template)
+    }
+
+    /// Formats a type the way it would usually be written, instead
+    /// of with the spacing that `TokenStream` uses between tokens.
+    fn written_type(ty: &syn::Type) -> String {
+        let mut name = quote! { #ty }.to_string();
+
+        for (from, to) in [
+            (" :: ", "::"),
+            (":: ", "::"),
+            (" ::", "::"),
+            (" < ", "<"),
+            (" <", "<"),
+            ("< ", "<"),
+            (" >", ">"),
+            (" ,", ","),
+            (" ;", ";"),
+            ("& ", "&"),
+            ("[ ", "["),
+            (" ]", "]"),
+            ("( ", "("),
+            (" )", ")"),
+        ] {
+            name = name.replace(from, to);
+        }
+
+        name
+    }
+
+    fn option(value: Option<impl ToTokens>) -> TokenStream {
+        match value {
+            Some(v) => quote! { ::core::option::Option::Some(#v) },
+            None => quote! { ::core::option::Option::None },
+        }
+    }
+}
+
+pub mod clone {
+    use super::{AllFieldNames, GenericsWithoutDefaults};
+    use crate::{Field, FieldContainer, SourceField};
+    use proc_macro2::TokenStream;
+    use quote::{quote, quote_spanned, ToTokens};
+
+    pub(crate) struct Clone<'a> {
+        pub(crate) arms: &'a [TokenStream],
+        pub(crate) original_generics: GenericsWithoutDefaults<'a>,
+        pub(crate) parameterized_error_name: &'a dyn ToTokens,
+        pub(crate) where_clauses: &'a [TokenStream],
+    }
+
+    impl ToTokens for Clone<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                arms,
+                original_generics,
+                parameterized_error_name,
+                where_clauses,
+            } = *self;
+
+            let type_params = original_generics.generics.type_params().map(|t| &t.ident);
+
+            let clone_impl = quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#original_generics> ::core::clone::Clone for #parameterized_error_name
+                where
+                    #(#type_params: ::core::clone::Clone,)*
+                    #(#where_clauses),*
+                {
+                    fn clone(&self) -> Self {
+                        match *self {
+                            #(#arms),*
+                        }
+                    }
+                }
+            };
+
+            stream.extend(clone_impl);
+        }
+    }
+
+    pub(crate) struct CloneMatchArm<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) field_container: &'a FieldContainer,
+        pub(crate) pattern_ident: &'a dyn ToTokens,
+    }
+
+    impl ToTokens for CloneMatchArm<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                field_container,
+                pattern_ident,
+            } = *self;
+
+            let field_names = AllFieldNames(field_container).field_names();
+            let field_names = field_names.iter();
+
+            // Spanned to the field so that a field that cannot be
+            // cloned is reported where it is declared.
+            let source_field = field_container
+                .selector_kind
+                .source_field()
+                .map(SourceField::name);
+            let backtrace_field = field_container.backtrace_field.as_ref().map(Field::name);
+
+            let cloned_fields = field_names.clone().map(|&name| {
+                let clone = if Some(name) == source_field {
+                    quote! { clone_source }
+                } else if Some(name) == backtrace_field {
+                    quote! { clone_backtrace }
+                } else {
+                    quote! { clone_field }
+                };
+
+                quote_spanned! { name.span()=>
+                    #name: #crate_root::__clone::#clone(&*#name)
+                }
+            });
+
+            let match_arm = quote! {
+                #pattern_ident { #(ref #field_names),* } => #pattern_ident {
+                    #(#cloned_fields),*
+                }
+            };
+
+            stream.extend(match_arm);
+        }
+    }
+}
+
 pub mod context_selector {
     use super::{GenericsWithoutDefaults, NoContextSelector, SourceInfo, StaticIdent};
     use crate::{ContextSelectorKind, Field, SuffixKind};
@@ -245,6 +517,9 @@ pub mod context_selector {
         pub visibility: Option<&'a dyn ToTokens>,
         pub where_clauses: &'a [TokenStream],
         pub default_suffix: &'a SuffixKind,
+        /// Generates the selector returned by the setters of a
+        /// non-builder selector's defaulted fields.
+        pub with_defaults: bool,
     }
 
     impl ToTokens for ContextSelector<'_> {
@@ -252,19 +527,42 @@ pub mod context_selector {
             use self::ContextSelectorKind::*;
 
             let context_selector = match self.selector_kind {
-                Context { source_field, .. } => {
+                Context {
+                    source_field,
+                    from_source,
+                    ..
+                } => {
                     let context_selector_type = self.generate_type();
+                    let context_selector_builder = if self.selector_kind.is_builder() {
+                        Some(self.generate_builder())
+                    } else {
+                        None
+                    };
                     let context_selector_impl = match source_field {
                         Some(_) => None,
                         None => Some(self.generate_leaf()),
                     };
                     let context_selector_into_error_impl =
                         self.generate_into_error(source_field.as_ref());
+                    let from_source_impl = match source_field {
+                        Some(source_field) if *from_source => {
+                            Some(self.generate_from_source_with_defaults(source_field))
+                        }
+                        _ => None,
+                    };
+                    let with_defaults_impl = if self.has_default_setters() {
+                        Some(self.generate_with_defaults(source_field.as_ref()))
+                    } else {
+                        None
+                    };
 
                     quote! {
                         #context_selector_type
+                        #context_selector_builder
                         #context_selector_impl
                         #context_selector_into_error_impl
+                        #from_source_impl
+                        #with_defaults_impl
                     }
                 }
                 Whatever {
@@ -279,36 +577,74 @@ pub mod context_selector {
     }
 
     impl ContextSelector<'_> {
+        /// Whether fields with a default value are stored in the
+        /// context selector, where they may be optionally set.
+        fn includes_defaulted_fields(&self) -> bool {
+            self.selector_kind.is_builder() || self.with_defaults
+        }
+
+        /// Whether the selector has setters for its defaulted fields
+        /// that return the selector's `WithDefaults` counterpart.
+        fn has_default_setters(&self) -> bool {
+            !self.includes_defaulted_fields()
+                && self.user_fields.iter().any(|f| f.default.is_some())
+        }
+
+        /// The user fields that are stored in the context selector.
+        ///
+        /// Fields with a default value are filled in when the error
+        /// is constructed, unless the selector includes defaulted
+        /// fields, in which case they may be optionally set.
+        fn selector_fields(&self) -> impl Iterator<Item = &Field> {
+            let includes_defaulted_fields = self.includes_defaulted_fields();
+            self.user_fields
+                .iter()
+                .filter(move |f| includes_defaulted_fields || f.default.is_none())
+        }
+
+        fn selector_name(&self) -> proc_macro2::Ident {
+            let selector_name = self
+                .selector_kind
+                .resolve_name(self.default_suffix, self.selector_base_name);
+
+            if self.with_defaults {
+                format_ident!("{}WithDefaults", selector_name)
+            } else {
+                selector_name
+            }
+        }
+
         fn user_field_generics(&self) -> Vec<proc_macro2::Ident> {
-            (0..self.user_fields.len())
+            (0..self.selector_fields().count())
                 .map(|i| format_ident!("__T{}", i))
                 .collect()
         }
 
         fn user_field_names(&self) -> Vec<&syn::Ident> {
-            self.user_fields
-                .iter()
+            self.selector_fields()
                 .map(|Field { name, .. }| name)
                 .collect()
         }
 
         fn parameterized_selector_name(&self) -> TokenStream {
-            let selector_name = self
-                .selector_kind
-                .resolve_name(self.default_suffix, self.selector_base_name);
+            let selector_name = self.selector_name();
             let user_generics = self.user_field_generics();
 
             quote! { #selector_name<#(#user_generics,)*> }
         }
 
         fn extended_where_clauses(&self) -> Vec<TokenStream> {
-            let user_fields = self.user_fields;
+            let crate_root = self.crate_root;
             let user_field_generics = self.user_field_generics();
             let where_clauses = self.where_clauses;
 
-            let target_types = user_fields
-                .iter()
-                .map(|Field { ty, .. }| quote! { ::core::convert::Into<#ty>});
+            let target_types = self.selector_fields().map(|Field { ty, default, .. }| {
+                if default.is_some() {
+                    quote! { #crate_root::context_builder::DefaultedField<#ty> }
+                } else {
+                    quote! { ::core::convert::Into<#ty> }
+                }
+            });
 
             user_field_generics
                 .into_iter()
@@ -319,10 +655,17 @@ pub mod context_selector {
         }
 
         fn transfer_user_fields(&self) -> Vec<TokenStream> {
-            self.user_field_names()
-                .into_iter()
-                .map(|name| {
-                    quote! { #name: ::core::convert::Into::into(self.#name) }
+            let crate_root = self.crate_root;
+            let includes_defaulted_fields = self.includes_defaulted_fields();
+
+            self.user_fields
+                .iter()
+                .map(|Field { name, default, .. }| match default {
+                    None => quote! { #name: ::core::convert::Into::into(self.#name) },
+                    Some(default) if includes_defaulted_fields => quote! {
+                        #name: #crate_root::context_builder::DefaultedField::resolve(self.#name, || #default)
+                    },
+                    Some(default) => quote! { #name: #default },
                 })
                 .collect()
         }
@@ -339,14 +682,24 @@ pub mod context_selector {
         fn construct_implicit_fields_with_source(&self) -> TokenStream {
             let crate_root = self.crate_root;
             let expression = quote! { {
-                use #crate_root::AsErrorSource;
+                use #crate_root::{AsErrorSource, __IsErrorCompat as _, __NotErrorCompat as _};
+                let compat = (&&#crate_root::__CompatRef(&error)).__snafu_source_compat();
                 let error = error.as_error_source();
-                #crate_root::GenerateImplicitData::generate_with_source(error)
+                #crate_root::GenerateImplicitData::__snafu_generate_with_compat(error, compat)
             } };
 
             self.construct_implicit_fields_with_expression(expression)
         }
 
+        fn construct_implicit_fields_with_expected(&self) -> TokenStream {
+            let crate_root = self.crate_root;
+            let expression = quote! {
+                #crate_root::GenerateImplicitData::generate_with_expected(expected)
+            };
+
+            self.construct_implicit_fields_with_expression(expression)
+        }
+
         fn construct_implicit_fields_with_expression(
             &self,
             expression: TokenStream,
@@ -368,6 +721,13 @@ pub mod context_selector {
             let user_field_names = self.user_field_names();
             let selector_doc_string = self.selector_doc_string;
 
+            // The fields of a builder are only set via its methods.
+            let field_visibility = if self.includes_defaulted_fields() {
+                None
+            } else {
+                visibility
+            };
+
             let body = if user_field_names.is_empty() {
                 quote! { ; }
             } else {
@@ -375,7 +735,7 @@ pub mod context_selector {
                     {
                         #(
                             #[allow(missing_docs)]
-                            #visibility #user_field_names: #user_field_generics
+                            #field_visibility #user_field_names: #user_field_generics
                         ),*
                     }
                 }
@@ -388,6 +748,205 @@ pub mod context_selector {
             }
         }
 
+        fn generate_builder(self) -> TokenStream {
+            let crate_root = self.crate_root;
+            let visibility = self.visibility;
+            let selector_name = self.selector_name();
+            let user_field_names = self.user_field_names();
+            let setters = self.generate_setters();
+
+            let missing = quote! { #crate_root::context_builder::Missing };
+            let all_missing = user_field_names.iter().map(|_| &missing);
+
+            quote! {
+                impl #selector_name<#(#all_missing,)*> {
+                    #[doc = "Create a context selector with none of its fields set"]
+                    #[must_use]
+                    #visibility fn new() -> Self {
+                        #selector_name {
+                            #(#user_field_names: #missing,)*
+                        }
+                    }
+                }
+
+                #setters
+            }
+        }
+
+        /// Setters for the fields stored in a selector that includes
+        /// defaulted fields. Required fields of a `WithDefaults`
+        /// selector have already been set, so only its defaulted
+        /// fields have setters.
+        fn generate_setters(self) -> TokenStream {
+            let visibility = self.visibility;
+            let selector_name = self.selector_name();
+            let user_field_generics = self.user_field_generics();
+            let user_field_names = self.user_field_names();
+
+            let setters = self
+                .selector_fields()
+                .enumerate()
+                .filter(|(_, field)| !self.with_defaults || field.default.is_some())
+                .map(|(i, field)| {
+                    let name = &field.name;
+                    let (setter_generics, arg_ty, value_ty, value) = self.setter_parts(field);
+
+                    let output_generics = user_field_generics.iter().enumerate().map(|(j, g)| {
+                        if i == j {
+                            value_ty.clone()
+                        } else {
+                            quote! { #g }
+                        }
+                    });
+
+                    let other_field_names = user_field_names.iter().filter(|&&n| n != name);
+
+                    let doc = format!("Set the `{}` field", name);
+
+                    quote! {
+                        #[doc = #doc]
+                        #[must_use]
+                        #visibility fn #name #setter_generics(self, #name: #arg_ty) -> #selector_name<#(#output_generics,)*> {
+                            #selector_name {
+                                #(#other_field_names: self.#other_field_names,)*
+                                #name: #value,
+                            }
+                        }
+                    }
+                });
+
+            quote! {
+                impl<#(#user_field_generics,)*> #selector_name<#(#user_field_generics,)*> {
+                    #(#setters)*
+                }
+            }
+        }
+
+        /// The generics, argument type, stored type, and stored value
+        /// of a field's setter.
+        fn setter_parts(
+            &self,
+            field: &Field,
+        ) -> (Option<TokenStream>, TokenStream, TokenStream, TokenStream) {
+            let crate_root = self.crate_root;
+            let generics = self.original_generics_without_defaults.generics;
+            let Field {
+                name, ty, default, ..
+            } = field;
+
+            // Setters accept anything that can be converted into the
+            // field, like the fields of a struct-literal selector.
+            // Fields that involve the error's generics can only be
+            // converted once the error type is known.
+            let setter_generic = format_ident!("__V");
+            let setter_generics = if mentions_generics(ty, generics) {
+                quote! { <#setter_generic> }
+            } else {
+                quote! { <#setter_generic: ::core::convert::Into<#ty>> }
+            };
+            let (setter_generics, arg_ty, value_ty) = (
+                Some(setter_generics),
+                quote! { #setter_generic },
+                quote! { #setter_generic },
+            );
+            let value = quote! { #name };
+
+            if default.is_some() {
+                (
+                    setter_generics,
+                    arg_ty,
+                    quote! { #crate_root::context_builder::Provided<#value_ty> },
+                    quote! { #crate_root::context_builder::Provided(#value) },
+                )
+            } else {
+                (setter_generics, arg_ty, value_ty, value)
+            }
+        }
+
+        /// Setters for the defaulted fields of a non-builder
+        /// selector. Each returns the selector's `WithDefaults`
+        /// counterpart, which stores every user field and can be used
+        /// everywhere the original selector can.
+        fn generate_with_defaults(self, source_field: Option<&crate::SourceField>) -> TokenStream {
+            let crate_root = self.crate_root;
+            let visibility = self.visibility;
+            let parameterized_selector_name = self.parameterized_selector_name();
+            let user_field_generics = self.user_field_generics();
+            let required_field_names = self.user_field_names();
+
+            let doc = format!(
+                "The `{}` context selector with some of its defaulted fields set",
+                self.selector_name(),
+            );
+            let with_defaults = ContextSelector {
+                selector_doc_string: &doc,
+                with_defaults: true,
+                ..self
+            };
+            let with_defaults_name = with_defaults.selector_name();
+            let with_defaults_type = with_defaults.generate_type();
+            let with_defaults_setters = with_defaults.generate_setters();
+            let with_defaults_impl = match source_field {
+                Some(_) => None,
+                None => Some(with_defaults.generate_leaf()),
+            };
+            let with_defaults_into_error_impl = with_defaults.generate_into_error(source_field);
+
+            let missing = quote! { #crate_root::context_builder::Missing };
+
+            let setters = self
+                .user_fields
+                .iter()
+                .filter(|field| field.default.is_some())
+                .map(|field| {
+                    let name = &field.name;
+                    let (setter_generics, arg_ty, value_ty, value) = self.setter_parts(field);
+
+                    let mut required_generics = user_field_generics.iter();
+                    let output_generics = self.user_fields.iter().map(|f| {
+                        if f.default.is_none() {
+                            let g = required_generics.next();
+                            quote! { #g }
+                        } else if f.name == *name {
+                            value_ty.clone()
+                        } else {
+                            missing.clone()
+                        }
+                    });
+
+                    let other_defaulted_field_names = self
+                        .user_fields
+                        .iter()
+                        .filter(|f| f.default.is_some() && f.name != *name)
+                        .map(|f| &f.name);
+
+                    let doc = format!("Set the `{}` field", name);
+
+                    quote! {
+                        #[doc = #doc]
+                        #[must_use]
+                        #visibility fn #name #setter_generics(self, #name: #arg_ty) -> #with_defaults_name<#(#output_generics,)*> {
+                            #with_defaults_name {
+                                #(#required_field_names: self.#required_field_names,)*
+                                #(#other_defaulted_field_names: #missing,)*
+                                #name: #value,
+                            }
+                        }
+                    }
+                });
+
+            quote! {
+                impl<#(#user_field_generics,)*> #parameterized_selector_name {
+                    #(#setters)*
+                }
+
+                #with_defaults_type
+                #with_defaults_setters
+                #with_defaults_impl
+                #with_defaults_into_error_impl
+            }
+        }
+
         fn generate_leaf(self) -> TokenStream {
             let error_constructor_name = self.error_constructor_name;
             let original_generics_without_defaults = self.original_generics_without_defaults;
@@ -470,6 +1029,29 @@ pub mod context_selector {
                 None => (quote! { #crate_root::NoneError }, None, None),
             };
 
+            // Only errors without a source can be created by
+            // `OptionExt::expect_context`.
+            let into_error_expecting = if source_field.is_none() {
+                let construct_implicit_fields = self.construct_implicit_fields_with_expected();
+
+                Some(quote! {
+                    #[track_caller]
+                    fn into_error_expecting(
+                        self,
+                        error: Self::Source,
+                        expected: #crate_root::Expected,
+                    ) -> #parameterized_error_name {
+                        let _error = error;
+                        #error_constructor_name {
+                            #construct_implicit_fields
+                            #(#transfer_user_fields),*
+                        }
+                    }
+                })
+            } else {
+                None
+            };
+
             quote! {
                 impl<#generics> #crate_root::IntoError<#parameterized_error_name> for #parameterized_selector_name
                 where
@@ -487,6 +1069,8 @@ pub mod context_selector {
                             #(#transfer_user_fields),*
                         }
                     }
+
+                    #into_error_expecting
                 }
             }
         }
@@ -522,57 +1106,414 @@ pub mod context_selector {
                 None => (quote! { #crate_root::NoneError }, None, None),
             };
 
-            let message_field_name = &message_field.name;
+            let message_field_name = &message_field.name;
+
+            quote! {
+                impl<#original_generics_without_defaults> #crate_root::FromString for #parameterized_error_name
+                where
+                    #(#extended_where_clauses),*
+                {
+                    type Source = #source_ty;
+
+                    #[track_caller]
+                    fn without_source(message: String) -> Self {
+                        #error_constructor_name {
+                            #construct_implicit_fields
+                            #empty_source_field
+                            #message_field_name: message,
+                        }
+                    }
+
+                    #[track_caller]
+                    fn with_source(error: Self::Source, message: String) -> Self {
+                        #error_constructor_name {
+                            #construct_implicit_fields_with_source
+                            #transfer_source_field
+                            #message_field_name: message,
+                        }
+                    }
+                }
+            }
+        }
+
+        fn generate_from_source(self, source_field: &crate::SourceField) -> TokenStream {
+            let user_field_generics = self.user_field_generics();
+            let user_field_generics = user_field_generics
+                .iter()
+                .map(|g| g as _)
+                .collect::<Vec<&dyn ToTokens>>();
+            let generics = self
+                .original_generics_without_defaults
+                .push(&user_field_generics);
+
+            let source_info = SourceInfo::from_source_field(source_field);
+
+            NoContextSelector {
+                source_info,
+                parameterized_error_name: self.parameterized_error_name,
+                generics,
+                where_clauses: self.where_clauses,
+                error_constructor_name: self.error_constructor_name,
+                construct_default_fields: quote! {},
+                construct_implicit_fields_with_source: self.construct_implicit_fields_with_source(),
+            }
+            .to_token_stream()
+        }
+
+        /// Implements `From` for the source when every context field
+        /// has a default value.
+        fn generate_from_source_with_defaults(
+            self,
+            source_field: &crate::SourceField,
+        ) -> TokenStream {
+            let construct_default_fields = self
+                .user_fields
+                .iter()
+                .flat_map(|Field { name, default, .. }| {
+                    let default = default.as_ref()?;
+                    Some(quote! { #name: #default, })
+                })
+                .collect();
+
+            NoContextSelector {
+                source_info: SourceInfo::from_source_field(source_field),
+                parameterized_error_name: self.parameterized_error_name,
+                generics: self.original_generics_without_defaults,
+                where_clauses: self.where_clauses,
+                error_constructor_name: self.error_constructor_name,
+                construct_default_fields,
+                construct_implicit_fields_with_source: self.construct_implicit_fields_with_source(),
+            }
+            .to_token_stream()
+        }
+    }
+
+    /// Whether the type refers to any of the generic parameters or
+    /// lifetimes of the error type.
+    fn mentions_generics(ty: &syn::Type, generics: &syn::Generics) -> bool {
+        fn walk(tokens: TokenStream, names: &[String]) -> bool {
+            tokens.into_iter().any(|tt| match tt {
+                proc_macro2::TokenTree::Ident(i) => names.iter().any(|n| i == n),
+                proc_macro2::TokenTree::Group(g) => walk(g.stream(), names),
+                _ => false,
+            })
+        }
+
+        let names = generics
+            .params
+            .iter()
+            .map(|p| match p {
+                syn::GenericParam::Lifetime(l) => l.lifetime.ident.to_string(),
+                syn::GenericParam::Type(t) => t.ident.to_string(),
+                syn::GenericParam::Const(c) => c.ident.to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        walk(ty.to_token_stream(), &names)
+    }
+}
+
+pub mod partial_eq {
+    use super::GenericsWithoutDefaults;
+    use crate::{Field, FieldContainer, SourceField};
+    use proc_macro2::TokenStream;
+    use quote::{format_ident, quote, quote_spanned, ToTokens};
+    use syn::spanned::Spanned as _;
+
+    pub(crate) struct PartialEq<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) arms: &'a [TokenStream],
+        /// Set when `Eq` is also implemented; every compared field
+        /// must implement it.
+        pub(crate) eq_field_types: Option<&'a [&'a syn::Type]>,
+        pub(crate) original_generics: GenericsWithoutDefaults<'a>,
+        pub(crate) parameterized_error_name: &'a dyn ToTokens,
+        pub(crate) where_clauses: &'a [TokenStream],
+    }
+
+    impl ToTokens for PartialEq<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                arms,
+                eq_field_types,
+                original_generics,
+                parameterized_error_name,
+                where_clauses,
+            } = *self;
+
+            let type_params = original_generics.generics.type_params().map(|t| &t.ident);
+
+            let partial_eq_impl = quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#original_generics> ::core::cmp::PartialEq for #parameterized_error_name
+                where
+                    #(#type_params: ::core::cmp::PartialEq,)*
+                    #(#where_clauses),*
+                {
+                    fn eq(&self, other: &Self) -> bool {
+                        #[allow(unused_imports)]
+                        use #crate_root::__partial_eq::{ByDisplay as _, ByPartialEq as _, SourceEq};
+
+                        #[allow(unreachable_patterns)]
+                        match (self, other) {
+                            #(#arms,)*
+                            _ => false,
+                        }
+                    }
+                }
+            };
+
+            stream.extend(partial_eq_impl);
+
+            if let Some(eq_field_types) = eq_field_types {
+                let type_params = original_generics.generics.type_params().map(|t| &t.ident);
+
+                // Spanned to the field type so that a field that is
+                // not `Eq` is reported where it is declared.
+                let eq_field_types = eq_field_types.iter().map(|ty| {
+                    quote_spanned! { ty.span()=> #ty: ::core::cmp::Eq }
+                });
+
+                let eq_impl = quote! {
+                    #[allow(single_use_lifetimes)]
+                    impl<#original_generics> ::core::cmp::Eq for #parameterized_error_name
+                    where
+                        #(#type_params: ::core::cmp::Eq,)*
+                        #(#eq_field_types,)*
+                        #(#where_clauses),*
+                    {}
+                };
+
+                stream.extend(eq_impl);
+            }
+        }
+    }
+
+    /// Compares the user fields, the message, and the source of
+    /// matching variants. Backtraces and implicit data are ignored.
+    pub(crate) struct PartialEqMatchArm<'a> {
+        pub(crate) field_container: &'a FieldContainer,
+        pub(crate) pattern_ident: &'a dyn ToTokens,
+    }
+
+    impl ToTokens for PartialEqMatchArm<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                field_container,
+                pattern_ident,
+            } = *self;
+
+            let selector_kind = &field_container.selector_kind;
+
+            let compared_fields = selector_kind
+                .user_fields()
+                .iter()
+                .chain(selector_kind.message_field())
+                .map(Field::name);
+
+            let mut field_names = Vec::new();
+            let mut self_names = Vec::new();
+            let mut other_names = Vec::new();
+            let mut comparisons = Vec::new();
+
+            for name in compared_fields {
+                let self_name = format_ident!("__self_{}", name);
+                let other_name = format_ident!("__other_{}", name);
+
+                comparisons.push(quote! { #self_name == #other_name });
+                field_names.push(name);
+                self_names.push(self_name);
+                other_names.push(other_name);
+            }
+
+            if let Some(SourceField { name, .. }) = selector_kind.source_field() {
+                let self_name = format_ident!("__self_{}", name);
+                let other_name = format_ident!("__other_{}", name);
+
+                let comparison = if selector_kind.is_whatever() {
+                    quote! {
+                        match (#self_name, #other_name) {
+                            (::core::option::Option::Some(a), ::core::option::Option::Some(b)) => {
+                                (&SourceEq(a, b)).source_eq()
+                            }
+                            (::core::option::Option::None, ::core::option::Option::None) => true,
+                            _ => false,
+                        }
+                    }
+                } else {
+                    quote! { (&SourceEq(#self_name, #other_name)).source_eq() }
+                };
+
+                comparisons.push(comparison);
+                field_names.push(name);
+                self_names.push(self_name);
+                other_names.push(other_name);
+            }
+
+            let match_arm = quote! {
+                (
+                    #pattern_ident { #(#field_names: #self_names,)* .. },
+                    #pattern_ident { #(#field_names: #other_names,)* .. },
+                ) => true #(&& #comparisons)*
+            };
+
+            stream.extend(match_arm);
+        }
+    }
+}
+
+pub mod exit_code {
+    use super::GenericsWithoutDefaults;
+    use crate::FieldContainer;
+    use proc_macro2::TokenStream;
+    use quote::{quote, ToTokens};
+
+    pub(crate) struct ExitCodeProvider<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) arms: &'a [TokenStream],
+        pub(crate) original_generics: GenericsWithoutDefaults<'a>,
+        pub(crate) parameterized_error_name: &'a dyn ToTokens,
+        pub(crate) where_clauses: &'a [TokenStream],
+    }
+
+    impl ToTokens for ExitCodeProvider<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                arms,
+                original_generics,
+                parameterized_error_name,
+                where_clauses,
+            } = *self;
+
+            let exit_code_impl = quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#original_generics> #crate_root::exit_code::ExitCodeProvider for #parameterized_error_name
+                where
+                    #(#where_clauses),*
+                {
+                    fn exit_code(&self) -> ::core::option::Option<#crate_root::exit_code::ExitCode> {
+                        match *self {
+                            #(#arms),*
+                        }
+                    }
+                }
+            };
+
+            stream.extend(exit_code_impl);
+        }
+    }
+
+    pub(crate) struct ExitCodeProviderMatchArm<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) field_container: &'a FieldContainer,
+        pub(crate) pattern_ident: &'a dyn ToTokens,
+    }
+
+    impl ToTokens for ExitCodeProviderMatchArm<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                field_container,
+                pattern_ident,
+            } = *self;
+
+            let source_field = field_container.selector_kind.source_field();
+
+            let match_arm = match (&field_container.exit_code, source_field) {
+                (Some(e), _) => quote! {
+                    #pattern_ident { .. } => ::core::option::Option::Some(::core::convert::From::from(#e))
+                },
+                // Use the exit code of the source, if it has one
+                (None, Some(source_field)) => {
+                    let source_name = source_field.name();
 
-            quote! {
-                impl<#original_generics_without_defaults> #crate_root::FromString for #parameterized_error_name
-                where
-                    #(#extended_where_clauses),*
-                {
-                    type Source = #source_ty;
+                    quote! {
+                        #pattern_ident { ref #source_name, .. } => {
+                            #[allow(unused_imports)]
+                            use #crate_root::exit_code::{__IsExitCodeProvider as _, __NotExitCodeProvider as _};
 
-                    #[track_caller]
-                    fn without_source(message: String) -> Self {
-                        #error_constructor_name {
-                            #construct_implicit_fields
-                            #empty_source_field
-                            #message_field_name: message,
+                            (&&#crate_root::exit_code::__SourceRef(#source_name)).__snafu_exit_code()
                         }
                     }
+                }
+                (None, None) => quote! {
+                    #pattern_ident { .. } => ::core::option::Option::None
+                },
+            };
 
-                    #[track_caller]
-                    fn with_source(error: Self::Source, message: String) -> Self {
-                        #error_constructor_name {
-                            #construct_implicit_fields_with_source
-                            #transfer_source_field
-                            #message_field_name: message,
+            stream.extend(match_arm);
+        }
+    }
+}
+
+pub mod severity {
+    use super::GenericsWithoutDefaults;
+    use crate::FieldContainer;
+    use proc_macro2::TokenStream;
+    use quote::{quote, ToTokens};
+
+    pub(crate) struct Severity<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) arms: &'a [TokenStream],
+        pub(crate) original_generics: GenericsWithoutDefaults<'a>,
+        pub(crate) parameterized_error_name: &'a dyn ToTokens,
+        pub(crate) where_clauses: &'a [TokenStream],
+    }
+
+    impl ToTokens for Severity<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                arms,
+                original_generics,
+                parameterized_error_name,
+                where_clauses,
+            } = *self;
+
+            let severity_impl = quote! {
+                #[allow(single_use_lifetimes)]
+                impl<#original_generics> #parameterized_error_name
+                where
+                    #(#where_clauses),*
+                {
+                    /// How severe this error is.
+                    #[allow(dead_code)]
+                    pub fn severity(&self) -> #crate_root::Severity {
+                        match *self {
+                            #(#arms),*
                         }
                     }
                 }
-            }
+            };
+
+            stream.extend(severity_impl);
         }
+    }
 
-        fn generate_from_source(self, source_field: &crate::SourceField) -> TokenStream {
-            let user_field_generics = self.user_field_generics();
-            let user_field_generics = user_field_generics
-                .iter()
-                .map(|g| g as _)
-                .collect::<Vec<&dyn ToTokens>>();
-            let generics = self
-                .original_generics_without_defaults
-                .push(&user_field_generics);
+    pub(crate) struct SeverityMatchArm<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) field_container: &'a FieldContainer,
+        pub(crate) pattern_ident: &'a dyn ToTokens,
+    }
 
-            let source_info = SourceInfo::from_source_field(source_field);
+    impl ToTokens for SeverityMatchArm<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                field_container,
+                pattern_ident,
+            } = *self;
 
-            NoContextSelector {
-                source_info,
-                parameterized_error_name: self.parameterized_error_name,
-                generics,
-                where_clauses: self.where_clauses,
-                error_constructor_name: self.error_constructor_name,
-                construct_implicit_fields_with_source: self.construct_implicit_fields_with_source(),
-            }
-            .to_token_stream()
+            // Every variant has a severity once any of them does
+            let level = field_container.severity.as_ref();
+
+            let match_arm = quote! {
+                #pattern_ident { .. } => #crate_root::Severity::#level
+            };
+
+            stream.extend(match_arm);
         }
     }
 }
@@ -589,6 +1530,7 @@ pub mod no_context_selector {
         pub generics: GenericsWithoutDefaults<'a>,
         pub where_clauses: &'a [TokenStream],
         pub error_constructor_name: &'a dyn ToTokens,
+        pub construct_default_fields: TokenStream,
         pub construct_implicit_fields_with_source: TokenStream,
     }
 
@@ -600,6 +1542,7 @@ pub mod no_context_selector {
                 generics,
                 where_clauses,
                 error_constructor_name,
+                construct_default_fields,
                 construct_implicit_fields_with_source,
             } = self;
 
@@ -629,6 +1572,7 @@ pub mod no_context_selector {
                     fn from(error: #from_type) -> Self {
                         #transform_source;
                         #error_constructor_name {
+                            #construct_default_fields
                             #construct_implicit_fields_with_source
                             #transfer_source_field
                         }
@@ -685,6 +1629,7 @@ pub mod display {
     }
 
     pub(crate) struct DisplayMatchArm<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
         pub(crate) field_container: &'a crate::FieldContainer,
         pub(crate) default_name: &'a dyn ToTokens,
         pub(crate) display_format: Option<&'a crate::Display>,
@@ -696,6 +1641,7 @@ pub mod display {
     impl ToTokens for DisplayMatchArm<'_> {
         fn to_tokens(&self, stream: &mut TokenStream) {
             let Self {
+                crate_root,
                 field_container,
                 default_name,
                 display_format,
@@ -722,13 +1668,24 @@ pub mod display {
 
             let mut shorthand_names = &BTreeSet::new();
             let mut assigned_names = &BTreeSet::new();
+            let mut placeholders: &[_] = &[];
 
-            let format = match (display_format, doc_comment) {
+            let literal_format = display_format.filter(|v| !v.exprs.is_empty());
+
+            let format = match (literal_format, doc_comment) {
                 (Some(v), _) => {
                     let exprs = &v.exprs;
                     shorthand_names = &v.shorthand_names;
                     assigned_names = &v.assigned_names;
-                    quote! { #(#exprs),* }
+                    placeholders = &v.placeholders;
+
+                    match &v.placeholder_format {
+                        Some(format) => {
+                            let args = &exprs[1..];
+                            quote! { #format #(, #args)* }
+                        }
+                        None => quote! { #(#exprs),* },
+                    }
                 }
                 (_, Some(d)) => {
                     let content = &d.content;
@@ -746,12 +1703,84 @@ pub mod display {
             let shorthand_fields = &shorthand_names & &field_names;
             let shorthand_fields = &shorthand_fields - &assigned_names;
 
-            let shorthand_assignments = quote! { #( #shorthand_fields = #shorthand_fields ),* };
+            let shorthand_assignments =
+                shorthand_fields
+                    .iter()
+                    .map(|name| match field_container.display_with(name) {
+                        Some(function) => {
+                            quote! { #name = #crate_root::fmt::with(#name, #function) }
+                        }
+                        None => quote! { #name = #name },
+                    });
+
+            let placeholder_assignments = placeholders.iter().map(|p| {
+                let crate::DisplayWithPlaceholder {
+                    placeholder,
+                    field,
+                    function,
+                } = p;
+                quote! { #placeholder = #crate_root::fmt::with(#field, #function) }
+            });
+
+            let shorthand_assignments = shorthand_assignments.chain(placeholder_assignments);
+            let shorthand_assignments = quote! { #(#shorthand_assignments),* };
 
-            let match_arm = quote! {
-                #pattern_ident { #(ref #field_names),* } => {
-                    write!(#FORMATTER_ARG, #format, #shorthand_assignments)
+            let message = quote! { write!(#FORMATTER_ARG, #format, #shorthand_assignments) };
+
+            let message = match display_format.and_then(|v| v.key.as_ref()) {
+                Some(key) => {
+                    let arg_fields = field_container
+                        .user_fields()
+                        .iter()
+                        .chain(&field_container.implicit_fields)
+                        .chain(selector_kind.message_field())
+                        .map(crate::Field::name);
+
+                    quote! {
+                        {
+                            use #crate_root::i18n::{__ArgRef, __ByDebug as _, __ByDisplay as _};
+
+                            let args = [
+                                #( (&&__ArgRef(#arg_fields)).__snafu_arg(stringify!(#arg_fields)) ),*
+                            ];
+
+                            match #crate_root::i18n::__localize(#key, &args, #FORMATTER_ARG) {
+                                ::core::option::Option::Some(r) => r,
+                                ::core::option::Option::None => #message,
+                            }
+                        }
+                    }
+                }
+                None => message,
+            };
+
+            let match_arm = match field_container.appended_source() {
+                Some(source_field) => {
+                    let source_field_name = source_field.name();
+
+                    let append = if field_container.selector_kind.is_whatever() {
+                        quote! {
+                            match #source_field_name {
+                                ::core::option::Option::Some(source) => write!(#FORMATTER_ARG, ": {}", source),
+                                ::core::option::Option::None => ::core::result::Result::Ok(()),
+                            }
+                        }
+                    } else {
+                        quote! { write!(#FORMATTER_ARG, ": {}", #source_field_name) }
+                    };
+
+                    quote! {
+                        #pattern_ident { #(ref #field_names),* } => {
+                            #message?;
+                            #append
+                        }
+                    }
                 }
+                None => quote! {
+                    #pattern_ident { #(ref #field_names),* } => {
+                        #message
+                    }
+                },
             };
 
             stream.extend(match_arm);
@@ -850,7 +1879,9 @@ pub mod error {
             let arm = match source_field {
                 Some(source_field) => {
                     let SourceField {
-                        name: field_name, ..
+                        name: field_name,
+                        is_shared,
+                        ..
                     } = source_field;
 
                     let convert_to_error_source = if selector_kind.is_whatever() {
@@ -861,6 +1892,11 @@ pub mod error {
                         quote! {
                             #field_name.as_error_source().source()
                         }
+                    } else if *is_shared {
+                        // Report the shared error itself, not the wrapper
+                        quote! {
+                            ::core::option::Option::Some((**#field_name).as_error_source())
+                        }
                     } else {
                         quote! {
                             ::core::option::Option::Some(#field_name.as_error_source())
@@ -1013,6 +2049,10 @@ pub mod error_compat {
         pub(crate) crate_root: &'a dyn ToTokens,
         pub(crate) parameterized_error_name: &'a dyn ToTokens,
         pub(crate) backtrace_arms: &'a [TokenStream],
+        pub(crate) location_arms: &'a [TokenStream],
+        pub(crate) location_trace_arms: &'a [TokenStream],
+        pub(crate) display_source_arms: &'a [TokenStream],
+        pub(crate) deepest_backtrace_arms: &'a [TokenStream],
         pub(crate) original_generics: GenericsWithoutDefaults<'a>,
         pub(crate) where_clauses: &'a [TokenStream],
     }
@@ -1023,10 +2063,30 @@ pub mod error_compat {
                 crate_root,
                 parameterized_error_name,
                 backtrace_arms,
+                location_arms,
+                location_trace_arms,
+                display_source_arms,
+                deepest_backtrace_arms,
                 original_generics,
                 where_clauses,
             } = *self;
 
+            let location_fn = quote! {
+                fn location(&self) -> ::core::option::Option<#crate_root::Location> {
+                    match *self {
+                        #(#location_arms),*
+                    }
+                }
+            };
+
+            let location_trace_fn = quote! {
+                fn location_trace(&self) -> ::core::option::Option<&#crate_root::LocationTrace> {
+                    match *self {
+                        #(#location_trace_arms),*
+                    }
+                }
+            };
+
             let backtrace_fn = quote! {
                 fn backtrace(&self) -> ::core::option::Option<&#crate_root::Backtrace> {
                     match *self {
@@ -1035,6 +2095,22 @@ pub mod error_compat {
                 }
             };
 
+            let display_source_fn = quote! {
+                fn display_includes_source(&self) -> ::core::option::Option<bool> {
+                    match *self {
+                        #(#display_source_arms),*
+                    }
+                }
+            };
+
+            let deepest_backtrace_fn = quote! {
+                fn deepest_backtrace(&self) -> ::core::option::Option<&#crate_root::Backtrace> {
+                    match *self {
+                        #(#deepest_backtrace_arms),*
+                    }
+                }
+            };
+
             let error_compat_impl = quote! {
                 #[allow(single_use_lifetimes)]
                 impl<#original_generics> #crate_root::ErrorCompat for #parameterized_error_name
@@ -1042,6 +2118,10 @@ pub mod error_compat {
                     #(#where_clauses),*
                 {
                     #backtrace_fn
+                    #location_fn
+                    #location_trace_fn
+                    #display_source_fn
+                    #deepest_backtrace_fn
                 }
             };
 
@@ -1095,4 +2175,160 @@ pub mod error_compat {
             stream.extend(match_arm);
         }
     }
+
+    pub(crate) struct ErrorCompatLocationMatchArm<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) field_container: &'a FieldContainer,
+        pub(crate) pattern_ident: &'a dyn ToTokens,
+    }
+
+    impl ToTokens for ErrorCompatLocationMatchArm<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                field_container:
+                    FieldContainer {
+                        implicit_fields, ..
+                    },
+                pattern_ident,
+            } = *self;
+
+            let field_names: Vec<_> = implicit_fields.iter().map(|f| &f.name).collect();
+
+            let match_arm = quote! {
+                #pattern_ident { #(ref #field_names,)* .. } => {
+                    #[allow(unused_imports)]
+                    use #crate_root::{__IsLocation as _, __NotLocation as _};
+
+                    ::core::option::Option::None
+                        #(.or_else(|| (&&#crate_root::__LocationRef(#field_names)).__snafu_location()))*
+                }
+            };
+
+            stream.extend(match_arm);
+        }
+    }
+
+    pub(crate) struct ErrorCompatDeepestBacktraceMatchArm<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) field_container: &'a FieldContainer,
+        pub(crate) pattern_ident: &'a dyn ToTokens,
+    }
+
+    impl ToTokens for ErrorCompatDeepestBacktraceMatchArm<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                field_container: FieldContainer { selector_kind, .. },
+                pattern_ident,
+            } = *self;
+
+            let match_arm = match selector_kind.source_field() {
+                Some(source_field) => {
+                    let SourceField {
+                        name: field_name, ..
+                    } = source_field;
+                    quote! {
+                        #pattern_ident { ref #field_name, .. } => {
+                            #[allow(unused_imports)]
+                            use #crate_root::{__IsErrorCompat as _, __NotErrorCompat as _};
+
+                            (&&#crate_root::__CompatRef(#field_name))
+                                .__snafu_deepest_backtrace()
+                                .or_else(|| #crate_root::ErrorCompat::backtrace(self))
+                        }
+                    }
+                }
+                None => {
+                    quote! {
+                        #pattern_ident { .. } => { #crate_root::ErrorCompat::backtrace(self) }
+                    }
+                }
+            };
+
+            stream.extend(match_arm);
+        }
+    }
+
+    pub(crate) struct ErrorCompatLocationTraceMatchArm<'a> {
+        pub(crate) crate_root: &'a dyn ToTokens,
+        pub(crate) field_container: &'a FieldContainer,
+        pub(crate) pattern_ident: &'a dyn ToTokens,
+    }
+
+    impl ToTokens for ErrorCompatLocationTraceMatchArm<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                crate_root,
+                field_container:
+                    FieldContainer {
+                        implicit_fields,
+                        selector_kind,
+                        is_transparent,
+                        ..
+                    },
+                pattern_ident,
+            } = *self;
+
+            let field_names: Vec<_> = implicit_fields.iter().map(|f| &f.name).collect();
+
+            // Transparent variants do not appear in the chain of
+            // errors, so the trace of the source is used when the
+            // variant doesn't have one of its own.
+            let (source_pattern, source_trace) = match selector_kind.source_field() {
+                Some(SourceField {
+                    name: source_name, ..
+                }) if *is_transparent => (
+                    quote! { ref #source_name, },
+                    quote! {
+                        .or_else(|| {
+                            #[allow(unused_imports)]
+                            use #crate_root::{__IsErrorCompat as _, __NotErrorCompat as _};
+
+                            (&&#crate_root::__CompatRef(#source_name)).__snafu_compat_location_trace()
+                        })
+                    },
+                ),
+                _ => (quote! {}, quote! {}),
+            };
+
+            let match_arm = quote! {
+                #pattern_ident { #(ref #field_names,)* #source_pattern .. } => {
+                    #[allow(unused_imports)]
+                    use #crate_root::{__IsLocationTrace as _, __NotLocationTrace as _};
+
+                    ::core::option::Option::None
+                        #(.or_else(|| (&&#crate_root::__LocationTraceRef(#field_names)).__snafu_location_trace()))*
+                        #source_trace
+                }
+            };
+
+            stream.extend(match_arm);
+        }
+    }
+
+    pub(crate) struct ErrorCompatDisplaySourceMatchArm<'a> {
+        pub(crate) field_container: &'a FieldContainer,
+        pub(crate) pattern_ident: &'a dyn ToTokens,
+    }
+
+    impl ToTokens for ErrorCompatDisplaySourceMatchArm<'_> {
+        fn to_tokens(&self, stream: &mut TokenStream) {
+            let Self {
+                field_container,
+                pattern_ident,
+            } = *self;
+
+            let includes_source = match field_container.display_includes_source() {
+                Some(v) => quote! { ::core::option::Option::Some(#v) },
+                None => quote! { ::core::option::Option::None },
+            };
+
+            let match_arm = quote! {
+                #pattern_ident { .. } => { #includes_source }
+            };
+
+            stream.extend(match_arm);
+        }
+    }
 }
@@ -0,0 +1,100 @@
+use heck::ToUpperCamelCase;
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    token, Ident, Item, Type,
+};
+
+pub fn body(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let wrapped =
+        syn::parse::Parser::parse(Punctuated::<Wrapped, token::Comma>::parse_terminated, attr)?;
+    let item = syn::parse::<Item>(item)?;
+
+    let mut e = match item {
+        Item::Enum(e) => e,
+        _ => {
+            return Err(syn::Error::new(
+                item.span(),
+                "`#[snafu::wrap]` may only be used on enums",
+            ))
+        }
+    };
+
+    for Wrapped { name, ty } in wrapped {
+        let name = match name {
+            Some(name) => name,
+            None => variant_name(&ty)?,
+        };
+
+        e.variants.push(syn::parse_quote! {
+            #[snafu(transparent)]
+            #name { source: #ty }
+        });
+    }
+
+    Ok(quote! { #e })
+}
+
+/// A wrapped error type, optionally preceded by the name of the
+/// variant that holds it: `Name = Type`.
+struct Wrapped {
+    name: Option<Ident>,
+    ty: Type,
+}
+
+impl Parse for Wrapped {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = if input.peek(Ident) && input.peek2(token::Eq) {
+            let name = input.parse()?;
+            input.parse::<token::Eq>()?;
+            Some(name)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            name,
+            ty: input.parse()?,
+        })
+    }
+}
+
+/// Names the variant after the wrapped type: `db::Error` becomes `Db`
+/// and `ParseIntError` becomes `ParseInt`.
+fn variant_name(ty: &Type) -> syn::Result<Ident> {
+    let not_nameable = || {
+        syn::Error::new(
+            ty.span(),
+            "Cannot name a variant for this type; use `Name = Type` instead",
+        )
+    };
+
+    let segments = match ty {
+        Type::Path(p) if p.qself.is_none() => &p.path.segments,
+        _ => return Err(not_nameable()),
+    };
+
+    let mut names = segments.iter().rev().map(|s| s.ident.to_string());
+    let last = names.next().ok_or_else(not_nameable)?;
+
+    let name = if last == "Error" {
+        names.next().ok_or_else(not_nameable)?
+    } else {
+        match last.strip_suffix("Error") {
+            Some(stem) if !stem.is_empty() => stem.to_owned(),
+            _ => last,
+        }
+    };
+
+    let name = name.trim_start_matches("r#").to_upper_camel_case();
+    let span = segments
+        .last()
+        .map_or_else(|| ty.span(), |s| s.ident.span());
+
+    Ok(format_ident!("{}", name, span = span))
+}
@@ -0,0 +1,606 @@
+//! Controlling when backtraces are captured and how they are shown.
+//!
+//! ## Capturing
+//!
+//! Optional backtraces (fields of type `Option<Backtrace>`) are
+//! captured according to a global [`Policy`][]. By default, the
+//! policy is [`Policy::Env`][], which uses the same environment
+//! variables as the standard library. The policy can be changed at
+//! any time with [`set_capture`][]:
+//!
+//! ```rust
+//! use snafu::{backtrace::{self, Policy}, prelude::*, Backtrace, ErrorCompat};
+//!
+//! #[derive(Debug, Snafu)]
+//! struct TimeoutError {
+//!     backtrace: Option<Backtrace>,
+//! }
+//!
+//! backtrace::set_capture(Policy::Always);
+//! assert!(ErrorCompat::backtrace(&TimeoutSnafu.build()).is_some());
+//!
+//! backtrace::set_capture(Policy::Never);
+//! assert!(ErrorCompat::backtrace(&TimeoutSnafu.build()).is_none());
+//! ```
+//!
+//! Backtraces in fields of type [`Backtrace`][] are always captured.
+//!
+//! When many errors are created at the same place, such as during a
+//! flood of identical failures, capturing a backtrace for each of
+//! them can dominate the CPU time. [`Policy::RateLimited`][] only
+//! captures a limited number of backtraces for each location within
+//! a time window:
+//!
+//! ```rust
+//! use snafu::backtrace::{self, Policy};
+//! use std::time::Duration;
+//!
+//! backtrace::set_capture(Policy::RateLimited {
+//!     limit: 10,
+//!     window: Duration::from_secs(60),
+//! });
+//! ```
+//!
+//! Use an [`OptionalBacktrace`][] field instead of `Option<Backtrace>`
+//! to find out if a backtrace was skipped because of the rate limit.
+//!
+//! ## Rendering
+//!
+//! A [`Report`][crate::Report] uses a [`Filter`][] to shorten the
+//! backtraces it prints. Frames from the standard library, common
+//! runtimes, and SNAFU itself are hidden and consecutive hidden frames
+//! are collapsed into a single line. When the report is created by
+//! the [`snafu::report`][macro@crate::report] procedural macro, frames
+//! from the crate using the macro are marked with `>`:
+//!
+//! ```text
+//! Backtrace:
+//!       ... 4 frames hidden
+//! >  4: my_app::load_config
+//!              at ./src/main.rs:12:5
+//! >  5: my_app::main
+//!              at ./src/main.rs:4:5
+//!       ... 9 frames hidden
+//! ```
+//!
+//! Set the `RUST_LIB_BACKTRACE` environment variable to `full` to
+//! print every frame instead.
+
+use crate::{once_bool::OnceBool, once_cell::OnceCell, Backtrace, Location};
+use core::{
+    convert::TryFrom,
+    fmt,
+    sync::atomic::{AtomicU32, AtomicU64, Ordering},
+    time::Duration,
+};
+use std::{
+    boxed::Box, collections::HashMap, env, string::String, sync::RwLock, time::Instant, vec::Vec,
+};
+
+/// When optional backtraces are captured.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Policy {
+    /// Every backtrace is captured.
+    Always,
+
+    /// No backtraces are captured.
+    Never,
+
+    /// Backtraces are captured when the environment variables used by
+    /// the standard library enable them.
+    ///
+    /// This looks first for the value of `RUST_LIB_BACKTRACE` then
+    /// `RUST_BACKTRACE`. Backtraces are enabled when the variable is
+    /// set to any value other than `0`, such as `1` or `full`.
+    ///
+    /// The environment variables are read only once per program
+    /// execution; changing them after they have been checked has no
+    /// effect.
+    Env,
+
+    /// One in every `N` backtraces is captured. `Sampled(0)` is the
+    /// same as [`Never`][Self::Never] and `Sampled(1)` is the same as
+    /// [`Always`][Self::Always].
+    Sampled(u32),
+
+    /// At most `limit` backtraces are captured for each location
+    /// where an error is created during each `window` of time. The
+    /// location is the same one that would be stored in an implicit
+    /// [`Location`][] field.
+    ///
+    /// Errors with an [`OptionalBacktrace`][] field record the
+    /// location when their backtrace was skipped.
+    RateLimited {
+        /// The number of backtraces captured for each location.
+        limit: u32,
+        /// How long the limit applies before it is reset.
+        ///
+        /// With a zero window, every error is in a window of its own
+        /// and is not counted: backtraces are always captured unless
+        /// `limit` is zero, in which case they are never captured.
+        window: Duration,
+    },
+}
+
+/// An optional backtrace that records why it was not captured.
+///
+/// This can be used in place of `Option<Backtrace>` for a backtrace
+/// field. It is captured according to the same [`Policy`][]:
+///
+/// ```rust
+/// use snafu::{backtrace::{self, OptionalBacktrace, Policy}, prelude::*, Location};
+/// use std::time::Duration;
+///
+/// #[derive(Debug, Snafu)]
+/// struct RequestError {
+///     backtrace: OptionalBacktrace,
+///     #[snafu(implicit)]
+///     location: Location,
+/// }
+///
+/// backtrace::set_capture(Policy::RateLimited {
+///     limit: 1,
+///     window: Duration::from_secs(60),
+/// });
+///
+/// let errors: Vec<_> = (0..2).map(|_| RequestSnafu.build()).collect();
+///
+/// assert!(matches!(errors[0].backtrace, OptionalBacktrace::Captured(_)));
+/// assert!(matches!(
+///     errors[1].backtrace,
+///     OptionalBacktrace::RateLimited(location) if location == errors[1].location,
+/// ));
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum OptionalBacktrace {
+    /// The backtrace was captured.
+    Captured(Backtrace),
+
+    /// The backtrace was not captured because of the current
+    /// [`Policy`][].
+    NotCaptured,
+
+    /// The backtrace was not captured because
+    /// [`Policy::RateLimited`][] had already captured its limit of
+    /// backtraces for this location.
+    RateLimited(Location),
+}
+
+// The kind of policy, including the rate of `Sampled`, is stored as
+// a single value. The limit and window of `RateLimited` do not fit in
+// it, so they are kept behind a lock that is written before
+// `RATE_LIMITED` is published. A thread that sees `RATE_LIMITED`
+// always reads a limit and window that were set together, although
+// they may come from a later call to `set_capture`.
+const NEVER: u32 = 0;
+const ALWAYS: u32 = 1;
+const RATE_LIMITED: u32 = u32::MAX - 1;
+const ENV: u32 = u32::MAX;
+
+static POLICY: AtomicU32 = AtomicU32::new(ENV);
+static SAMPLED: AtomicU32 = AtomicU32::new(0);
+static RATE_LIMIT: RwLock<RateLimit> = RwLock::new(RateLimit {
+    limit: 0,
+    window: Duration::ZERO,
+});
+
+#[derive(Copy, Clone)]
+struct RateLimit {
+    limit: u32,
+    window: Duration,
+}
+
+impl RateLimit {
+    fn get() -> Self {
+        *RATE_LIMIT.read().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Changes when optional backtraces are captured.
+///
+/// The new policy applies to every thread.
+pub fn set_capture(policy: Policy) {
+    let value = match policy {
+        Policy::Always => ALWAYS,
+        Policy::Never => NEVER,
+        Policy::Env => ENV,
+        Policy::Sampled(n) => n.min(RATE_LIMITED - 1),
+        Policy::RateLimited { limit, window } => {
+            let mut rate_limit = RATE_LIMIT.write().unwrap_or_else(|e| e.into_inner());
+            *rate_limit = RateLimit { limit, window };
+            Site::reset_all();
+            RATE_LIMITED
+        }
+    };
+
+    POLICY.store(value, Ordering::Release);
+}
+
+/// The current policy for capturing optional backtraces.
+pub fn capture() -> Policy {
+    match POLICY.load(Ordering::Acquire) {
+        ALWAYS => Policy::Always,
+        NEVER => Policy::Never,
+        ENV => Policy::Env,
+        RATE_LIMITED => {
+            let RateLimit { limit, window } = RateLimit::get();
+            Policy::RateLimited { limit, window }
+        }
+        n => Policy::Sampled(n),
+    }
+}
+
+/// Why an optional backtrace was not captured.
+pub(crate) enum Skipped {
+    ByPolicy,
+    RateLimited,
+}
+
+/// Decides if a backtrace for an error created at `location` should
+/// be captured now, according to the current policy.
+pub(crate) fn check_capture(location: Location) -> Result<(), Skipped> {
+    let enabled = match POLICY.load(Ordering::Acquire) {
+        ALWAYS => true,
+        NEVER => false,
+        ENV => env_enabled(),
+        RATE_LIMITED => {
+            return if Site::at(location).allow(RateLimit::get()) {
+                Ok(())
+            } else {
+                Err(Skipped::RateLimited)
+            }
+        }
+        n => SAMPLED.fetch_add(1, Ordering::Relaxed) % n == 0,
+    };
+
+    if enabled {
+        Ok(())
+    } else {
+        Err(Skipped::ByPolicy)
+    }
+}
+
+// Sites are spread across several maps so that errors created at
+// different locations rarely wait for the same lock. Once a site has
+// been found, it is only updated atomically.
+const SHARDS: usize = 16;
+
+type Shard = RwLock<Option<HashMap<core::panic::Location<'static>, &'static Site>>>;
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY_SHARD: Shard = RwLock::new(None);
+
+static SITES: [Shard; SHARDS] = [EMPTY_SHARD; SHARDS];
+
+// The window that a site has not counted any captures in.
+const NO_WINDOW: u64 = u64::MAX;
+
+/// The rate limit of a single location.
+struct Site {
+    window: AtomicU64,
+    captured: AtomicU32,
+}
+
+impl Site {
+    /// Finds the site for `location`, creating it the first time an
+    /// error is created there. A site is never freed, as there are
+    /// only as many of them as there are locations in the program.
+    fn at(location: Location) -> &'static Self {
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        location.hash(&mut hasher);
+        let shard = &SITES[hasher.finish() as usize % SHARDS];
+
+        let sites = shard.read().unwrap_or_else(|e| e.into_inner());
+        if let Some(site) = sites.as_ref().and_then(|sites| sites.get(location)) {
+            return site;
+        }
+        drop(sites);
+
+        let mut sites = shard.write().unwrap_or_else(|e| e.into_inner());
+        sites
+            .get_or_insert_with(HashMap::new)
+            .entry(*location)
+            .or_insert_with(|| {
+                Box::leak(Box::new(Site {
+                    window: AtomicU64::new(NO_WINDOW),
+                    captured: AtomicU32::new(0),
+                }))
+            })
+    }
+
+    fn reset_all() {
+        for shard in &SITES {
+            let sites = shard.read().unwrap_or_else(|e| e.into_inner());
+            for site in sites.iter().flat_map(|sites| sites.values()) {
+                site.window.store(NO_WINDOW, Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn allow(&self, rate_limit: RateLimit) -> bool {
+        let RateLimit { limit, window } = rate_limit;
+        let window = u64::try_from(window.as_nanos()).unwrap_or(u64::MAX);
+
+        if window == 0 {
+            return limit > 0;
+        }
+
+        static START: OnceCell<Instant> = OnceCell::new();
+        let elapsed = START.get_or_init(Instant::now).elapsed().as_nanos();
+        let current = u64::try_from(elapsed / u128::from(window)).unwrap_or(NO_WINDOW - 1);
+
+        // Only the thread that moves the site into a new window
+        // resets the count.
+        let previous = self.window.load(Ordering::Relaxed);
+        if previous != current
+            && self
+                .window
+                .compare_exchange(previous, current, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+        {
+            self.captured.store(0, Ordering::Relaxed);
+        }
+
+        self.captured
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| {
+                if n < limit {
+                    Some(n + 1)
+                } else {
+                    None
+                }
+            })
+            .is_ok()
+    }
+}
+
+fn env_enabled() -> bool {
+    static ENABLED: OnceBool = OnceBool::new();
+
+    ENABLED.get(|| {
+        env::var_os("RUST_LIB_BACKTRACE")
+            .or_else(|| env::var_os("RUST_BACKTRACE"))
+            .map_or(false, |v| v != "0")
+    })
+}
+
+/// Prefixes of the functions that are hidden by [`Filter::new`][].
+pub const DEFAULT_HIDDEN: &[&str] = &[
+    "std::",
+    "core::",
+    "alloc::",
+    "backtrace::backtrace::",
+    "backtrace::capture::",
+    "snafu::",
+    "test::",
+    "tokio::",
+    "futures::",
+    "futures_core::",
+    "futures_util::",
+    "__rust",
+    "__libc_start",
+    "_start",
+    "rust_begin_unwind",
+    "<unknown>",
+];
+
+/// Rules for which frames of a backtrace are shown.
+///
+/// Each frame is matched against the rules using the name of its
+/// function. Frames matching a prefix passed to [`hide`][Self::hide]
+/// are hidden unless they also match a prefix passed to
+/// [`show`][Self::show]. Shown frames matching a prefix passed to
+/// [`highlight`][Self::highlight] are marked.
+///
+/// ```rust
+/// use snafu::{backtrace::Filter, Report};
+/// # use snafu::prelude::*;
+/// # #[derive(Debug, Snafu)]
+/// # struct PlaceholderError;
+///
+/// fn main() -> Report<PlaceholderError> {
+///     Report::capture(|| Ok(())).with_backtrace_filter(
+///         Filter::new()
+///             .hide("hyper::")
+///             .show("tokio::fs::")
+///             .highlight(concat!(env!("CARGO_CRATE_NAME"), "::")),
+///     )
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Filter {
+    hidden: Vec<&'static str>,
+    shown: Vec<&'static str>,
+    highlighted: Vec<&'static str>,
+    highlighted_crates: Vec<&'static str>,
+}
+
+impl Filter {
+    /// Hides the frames in [`DEFAULT_HIDDEN`][].
+    pub fn new() -> Self {
+        Self {
+            hidden: DEFAULT_HIDDEN.to_vec(),
+            ..Self::empty()
+        }
+    }
+
+    /// Hides no frames.
+    pub fn empty() -> Self {
+        Self {
+            hidden: Vec::new(),
+            shown: Vec::new(),
+            highlighted: Vec::new(),
+            highlighted_crates: Vec::new(),
+        }
+    }
+
+    /// Hides frames whose function starts with `prefix`.
+    pub fn hide(mut self, prefix: &'static str) -> Self {
+        self.hidden.push(prefix);
+        self
+    }
+
+    /// Shows frames whose function starts with `prefix`, even when
+    /// they would otherwise be hidden.
+    pub fn show(mut self, prefix: &'static str) -> Self {
+        self.shown.push(prefix);
+        self
+    }
+
+    /// Marks frames whose function starts with `prefix`.
+    pub fn highlight(mut self, prefix: &'static str) -> Self {
+        self.highlighted.push(prefix);
+        self
+    }
+
+    /// Marks frames whose function is in the crate named `name`.
+    pub(crate) fn highlight_crate(mut self, name: &'static str) -> Self {
+        self.highlighted_crates.push(name);
+        self
+    }
+
+    /// Displays the backtrace using these rules.
+    ///
+    /// Every frame is shown when `RUST_LIB_BACKTRACE` is set to
+    /// `full`.
+    pub fn display<'a>(&'a self, backtrace: &'a Backtrace) -> Filtered<'a> {
+        Filtered {
+            filter: self,
+            backtrace,
+        }
+    }
+
+    fn is_hidden(&self, function: &str) -> bool {
+        matches_any(&self.hidden, function) && !matches_any(&self.shown, function)
+    }
+
+    fn is_highlighted(&self, function: &str) -> bool {
+        let unqualified = function.trim_start_matches('<');
+        let in_crate = |name: &&str| {
+            unqualified
+                .strip_prefix(*name)
+                .map_or(false, |rest| rest.starts_with("::"))
+        };
+
+        matches_any(&self.highlighted, function) || self.highlighted_crates.iter().any(in_crate)
+    }
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn matches_any(prefixes: &[&str], function: &str) -> bool {
+    let unqualified = function.trim_start_matches('<');
+
+    prefixes
+        .iter()
+        .any(|p| function.starts_with(p) || unqualified.starts_with(p))
+}
+
+/// A backtrace displayed using a [`Filter`][].
+///
+/// Created by [`Filter::display`][].
+pub struct Filtered<'a> {
+    filter: &'a Filter,
+    backtrace: &'a Backtrace,
+}
+
+impl fmt::Display for Filtered<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use core::fmt::Write as _;
+
+        let mut text = String::new();
+
+        #[cfg(not(feature = "backtraces-impl-backtrace-crate"))]
+        write!(text, "{}", self.backtrace)?;
+
+        #[cfg(feature = "backtraces-impl-backtrace-crate")]
+        write!(text, "{:?}", self.backtrace)?;
+
+        if full_backtraces_enabled() {
+            return text.lines().try_for_each(|line| writeln!(f, "{}", line));
+        }
+
+        let mut hidden = 0;
+
+        for frame in frames(&text) {
+            match frame {
+                Frame::Other(line) => writeln!(f, "{}", line)?,
+                Frame::Function { function, lines } => {
+                    if self.filter.is_hidden(function) {
+                        hidden += 1;
+                        continue;
+                    }
+
+                    write_hidden(f, &mut hidden)?;
+
+                    let highlight = self.filter.is_highlighted(function);
+                    for (i, line) in lines.iter().enumerate() {
+                        match line.strip_prefix(' ') {
+                            Some(rest) if highlight && i == 0 => writeln!(f, ">{}", rest)?,
+                            _ => writeln!(f, "{}", line)?,
+                        }
+                    }
+                }
+            }
+        }
+
+        write_hidden(f, &mut hidden)
+    }
+}
+
+fn write_hidden(f: &mut fmt::Formatter<'_>, hidden: &mut usize) -> fmt::Result {
+    match core::mem::take(hidden) {
+        0 => Ok(()),
+        1 => writeln!(f, "      ... 1 frame hidden"),
+        n => writeln!(f, "      ... {} frames hidden", n),
+    }
+}
+
+enum Frame<'a> {
+    Function {
+        function: &'a str,
+        lines: Vec<&'a str>,
+    },
+    Other(&'a str),
+}
+
+/// Splits the text of a backtrace into frames. A frame starts with
+/// a line like `  12: my_app::main` and continues with the
+/// indented lines that follow it, such as the source location.
+fn frames(text: &str) -> Vec<Frame<'_>> {
+    let mut frames = Vec::new();
+
+    for line in text.lines() {
+        let function = line
+            .trim_start()
+            .split_once(": ")
+            .filter(|(n, _)| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+            .map(|(_, function)| function.trim());
+
+        match (function, frames.last_mut()) {
+            (Some(function), _) => frames.push(Frame::Function {
+                function,
+                lines: std::vec![line],
+            }),
+            (None, Some(Frame::Function { lines, .. })) if line.starts_with(' ') => {
+                lines.push(line)
+            }
+            (None, _) => frames.push(Frame::Other(line)),
+        }
+    }
+
+    frames
+}
+
+fn full_backtraces_enabled() -> bool {
+    static ENABLED: OnceBool = OnceBool::new();
+
+    ENABLED.get(|| env::var_os("RUST_LIB_BACKTRACE").map_or(false, |v| v == "full"))
+}
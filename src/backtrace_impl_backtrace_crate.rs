@@ -1,5 +1,11 @@
 pub use backtrace::Backtrace;
 
+use crate::once_cell::OnceCell;
+use core::fmt;
+
+// The backtrace crate always requires the standard library.
+extern crate std;
+
 impl crate::GenerateImplicitData for Backtrace {
     fn generate() -> Self {
         Backtrace::new()
@@ -11,3 +17,60 @@ impl crate::AsBacktrace for Backtrace {
         Some(self)
     }
 }
+
+/// A backtrace whose symbols are resolved when it is first used.
+///
+/// Capturing the stack frames is relatively cheap, while resolving
+/// the frames to function names, files, and lines is not. This type
+/// only captures the frames when the error is created; symbols are
+/// resolved the first time the backtrace is displayed or retrieved
+/// with [`AsBacktrace`][crate::AsBacktrace]. This is useful for
+/// errors that are often created and then discarded.
+pub struct LazyBacktrace {
+    unresolved: Backtrace,
+    resolved: OnceCell<Backtrace>,
+}
+
+impl LazyBacktrace {
+    /// Captures the stack frames of the current thread without
+    /// resolving their symbols.
+    pub fn capture() -> Self {
+        Self {
+            unresolved: Backtrace::new_unresolved(),
+            resolved: OnceCell::new(),
+        }
+    }
+
+    /// Resolves the symbols, if needed, and returns the backtrace.
+    pub fn resolved(&self) -> &Backtrace {
+        self.resolved.get_or_init(|| {
+            let mut backtrace = self.unresolved.clone();
+            backtrace.resolve();
+            backtrace
+        })
+    }
+}
+
+impl crate::GenerateImplicitData for LazyBacktrace {
+    fn generate() -> Self {
+        Self::capture()
+    }
+}
+
+impl crate::AsBacktrace for LazyBacktrace {
+    fn as_backtrace(&self) -> Option<&Backtrace> {
+        Some(self.resolved())
+    }
+}
+
+impl fmt::Debug for LazyBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.resolved(), f)
+    }
+}
+
+impl fmt::Display for LazyBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.resolved(), f)
+    }
+}
@@ -24,3 +24,41 @@ impl fmt::Display for Backtrace {
         write!(f, "disabled backtrace")
     }
 }
+
+/// A backtrace whose symbols are resolved when it is first used.
+///
+/// Backtrace functionality is currently **disabled**. Please review
+/// [the feature flags](crate::guide::feature_flags) to enable it.
+#[derive(Debug)]
+pub struct LazyBacktrace(Backtrace);
+
+impl LazyBacktrace {
+    /// Captures the stack frames of the current thread without
+    /// resolving their symbols.
+    pub fn capture() -> Self {
+        LazyBacktrace(Backtrace(()))
+    }
+
+    /// Resolves the symbols, if needed, and returns the backtrace.
+    pub fn resolved(&self) -> &Backtrace {
+        &self.0
+    }
+}
+
+impl crate::GenerateImplicitData for LazyBacktrace {
+    fn generate() -> Self {
+        Self::capture()
+    }
+}
+
+impl crate::AsBacktrace for LazyBacktrace {
+    fn as_backtrace(&self) -> Option<&Backtrace> {
+        Some(self.resolved())
+    }
+}
+
+impl fmt::Display for LazyBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
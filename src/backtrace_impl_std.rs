@@ -1,5 +1,7 @@
 pub use std::backtrace::Backtrace;
 
+use core::fmt;
+
 impl crate::GenerateImplicitData for Backtrace {
     fn generate() -> Self {
         Backtrace::force_capture()
@@ -11,3 +13,69 @@ impl crate::AsBacktrace for Backtrace {
         Some(self)
     }
 }
+
+/// A backtrace whose symbols are resolved when it is first used.
+///
+/// Capturing the stack frames is relatively cheap, while resolving
+/// the frames to function names, files, and lines is not. This type
+/// only captures the frames when the error is created; symbols are
+/// resolved the first time the backtrace is displayed or retrieved
+/// with [`AsBacktrace`][crate::AsBacktrace]. This is useful for
+/// errors that are often created and then discarded.
+///
+/// The standard library's [`Backtrace`][] already resolves symbols
+/// on demand, so this type behaves the same as `Backtrace` with this
+/// backend.
+///
+/// Use it in place of [`Backtrace`][] as the type of a backtrace
+/// field:
+///
+/// ```rust
+/// use snafu::{prelude::*, ErrorCompat, LazyBacktrace};
+///
+/// #[derive(Debug, Snafu)]
+/// struct ParseError {
+///     backtrace: LazyBacktrace,
+/// }
+///
+/// let e = ParseSnafu.build();
+/// assert!(ErrorCompat::backtrace(&e).is_some());
+/// ```
+pub struct LazyBacktrace(Backtrace);
+
+impl LazyBacktrace {
+    /// Captures the stack frames of the current thread without
+    /// resolving their symbols.
+    pub fn capture() -> Self {
+        LazyBacktrace(Backtrace::force_capture())
+    }
+
+    /// Resolves the symbols, if needed, and returns the backtrace.
+    pub fn resolved(&self) -> &Backtrace {
+        &self.0
+    }
+}
+
+impl crate::GenerateImplicitData for LazyBacktrace {
+    fn generate() -> Self {
+        Self::capture()
+    }
+}
+
+impl crate::AsBacktrace for LazyBacktrace {
+    fn as_backtrace(&self) -> Option<&Backtrace> {
+        Some(self.resolved())
+    }
+}
+
+impl fmt::Debug for LazyBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.resolved(), f)
+    }
+}
+
+impl fmt::Display for LazyBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.resolved(), f)
+    }
+}
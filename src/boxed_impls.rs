@@ -3,7 +3,7 @@ use alloc::{boxed::Box, rc::Rc};
 #[cfg(target_has_atomic = "ptr")]
 use alloc::sync::Arc;
 
-use super::{AsBacktrace, Backtrace, ErrorCompat, GenerateImplicitData};
+use super::{AsBacktrace, Backtrace, ErrorCompat, Expected, GenerateImplicitData, Location};
 
 impl<E> ErrorCompat for Box<E>
 where
@@ -12,6 +12,22 @@ where
     fn backtrace(&self) -> Option<&Backtrace> {
         (**self).backtrace()
     }
+
+    fn location(&self) -> Option<Location> {
+        (**self).location()
+    }
+
+    fn location_trace(&self) -> Option<&crate::LocationTrace> {
+        (**self).location_trace()
+    }
+
+    fn display_includes_source(&self) -> Option<bool> {
+        (**self).display_includes_source()
+    }
+
+    fn deepest_backtrace(&self) -> Option<&Backtrace> {
+        (**self).deepest_backtrace()
+    }
 }
 
 impl<T> GenerateImplicitData for Box<T>
@@ -24,12 +40,20 @@ where
     }
 
     #[track_caller]
-    fn generate_with_source(source: &dyn crate::Error) -> Self
+    fn generate_with_source(source: &(dyn crate::Error + 'static)) -> Self
     where
         Self: Sized,
     {
         Box::new(T::generate_with_source(source))
     }
+
+    #[track_caller]
+    fn generate_with_expected(expected: Expected) -> Self
+    where
+        Self: Sized,
+    {
+        Box::new(T::generate_with_expected(expected))
+    }
 }
 
 impl<T> GenerateImplicitData for Rc<T>
@@ -42,12 +66,20 @@ where
     }
 
     #[track_caller]
-    fn generate_with_source(source: &dyn crate::Error) -> Self
+    fn generate_with_source(source: &(dyn crate::Error + 'static)) -> Self
     where
         Self: Sized,
     {
         Rc::new(T::generate_with_source(source))
     }
+
+    #[track_caller]
+    fn generate_with_expected(expected: Expected) -> Self
+    where
+        Self: Sized,
+    {
+        Rc::new(T::generate_with_expected(expected))
+    }
 }
 
 #[cfg(target_has_atomic = "ptr")]
@@ -61,12 +93,20 @@ where
     }
 
     #[track_caller]
-    fn generate_with_source(source: &dyn crate::Error) -> Self
+    fn generate_with_source(source: &(dyn crate::Error + 'static)) -> Self
     where
         Self: Sized,
     {
         Arc::new(T::generate_with_source(source))
     }
+
+    #[track_caller]
+    fn generate_with_expected(expected: Expected) -> Self
+    where
+        Self: Sized,
+    {
+        Arc::new(T::generate_with_expected(expected))
+    }
 }
 
 impl<T> AsBacktrace for Box<T>
@@ -0,0 +1,186 @@
+//! Describing every error a type can represent.
+//!
+//! Adding `#[snafu(catalog)]` to an error generates an associated
+//! constant `CATALOG` containing an [`ErrorDescriptor`][] for each
+//! variant (or for the struct itself). The descriptors can be
+//! rendered with [`to_markdown`][] or [`to_json`][], for example from
+//! a `build.rs` script or a test that writes out a catalog file.
+//!
+//! ```rust
+//! # #[cfg(feature = "alloc")] {
+//! use snafu::prelude::*;
+//!
+//! #[derive(Debug, Snafu)]
+//! #[snafu(catalog)]
+//! enum Error {
+//!     /// The user could not be found.
+//!     #[snafu(display("User {id} does not exist"))]
+//!     UserNotFound { id: u32 },
+//! }
+//!
+//! let markdown = snafu::catalog::to_markdown(Error::CATALOG);
+//! assert!(markdown.contains("## `Error::UserNotFound`"));
+//! assert!(markdown.contains("User {id} does not exist"));
+//! # }
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// A description of one error, generated by `#[snafu(catalog)]`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ErrorDescriptor {
+    /// The name of the error type.
+    pub type_name: &'static str,
+    /// The name of the enum variant, or `None` for a struct.
+    pub variant_name: Option<&'static str>,
+    /// The summary of the documentation comment, if any.
+    pub doc: Option<&'static str>,
+    /// The template used for `Display`, or `None` if the error is
+    /// transparent and displays its source instead.
+    pub display: Option<&'static str>,
+    /// The localization key from `display(key = "...")`, if any.
+    pub key: Option<&'static str>,
+    /// Every field except for the source.
+    pub fields: &'static [FieldDescriptor],
+    /// The type of the source field, if any.
+    pub source: Option<&'static str>,
+}
+
+/// A description of one field of an error.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FieldDescriptor {
+    /// The name of the field.
+    pub name: &'static str,
+    /// The type of the field, as written in the source code.
+    pub ty: &'static str,
+}
+
+/// Renders the descriptors as a Markdown document with one section
+/// per error.
+#[cfg(feature = "alloc")]
+pub fn to_markdown(descriptors: &[ErrorDescriptor]) -> String {
+    use core::fmt::Write;
+
+    let mut output = String::new();
+
+    for (i, d) in descriptors.iter().enumerate() {
+        if i != 0 {
+            output.push('\n');
+        }
+
+        // Writing to a `String` cannot fail
+        let _ = match d.variant_name {
+            Some(variant_name) => writeln!(output, "## `{}::{}`", d.type_name, variant_name),
+            None => writeln!(output, "## `{}`", d.type_name),
+        };
+
+        if let Some(doc) = d.doc {
+            let _ = write!(output, "\n{}\n", doc);
+        }
+
+        output.push('\n');
+
+        match d.display {
+            Some(display) => {
+                let _ = writeln!(output, "- **Message:** `{}`", display);
+            }
+            None => output.push_str("- **Message:** *(displays the source error)*\n"),
+        }
+
+        if let Some(key) = d.key {
+            let _ = writeln!(output, "- **Key:** `{}`", key);
+        }
+
+        if let Some(source) = d.source {
+            let _ = writeln!(output, "- **Source:** `{}`", source);
+        }
+
+        if !d.fields.is_empty() {
+            output.push_str("\n| Field | Type |\n|-------|------|\n");
+            for f in d.fields {
+                let _ = writeln!(output, "| `{}` | `{}` |", f.name, f.ty);
+            }
+        }
+    }
+
+    output
+}
+
+/// Renders the descriptors as a JSON array with one object per
+/// error.
+///
+/// Each object has the keys `type`, `variant`, `doc`, `display`,
+/// `key`, `fields`, and `source`. Absent values are `null`. `fields`
+/// is an array of objects with the keys `name` and `type`.
+#[cfg(feature = "alloc")]
+pub fn to_json(descriptors: &[ErrorDescriptor]) -> String {
+    let mut output = String::from("[");
+
+    for (i, d) in descriptors.iter().enumerate() {
+        if i != 0 {
+            output.push(',');
+        }
+
+        output.push_str("\n  {\"type\": ");
+        json_string(&mut output, d.type_name);
+        output.push_str(", \"variant\": ");
+        json_option(&mut output, d.variant_name);
+        output.push_str(", \"doc\": ");
+        json_option(&mut output, d.doc);
+        output.push_str(", \"display\": ");
+        json_option(&mut output, d.display);
+        output.push_str(", \"key\": ");
+        json_option(&mut output, d.key);
+        output.push_str(", \"fields\": [");
+        for (i, f) in d.fields.iter().enumerate() {
+            if i != 0 {
+                output.push_str(", ");
+            }
+            output.push_str("{\"name\": ");
+            json_string(&mut output, f.name);
+            output.push_str(", \"type\": ");
+            json_string(&mut output, f.ty);
+            output.push('}');
+        }
+        output.push_str("], \"source\": ");
+        json_option(&mut output, d.source);
+        output.push('}');
+    }
+
+    if !descriptors.is_empty() {
+        output.push('\n');
+    }
+    output.push_str("]\n");
+    output
+}
+
+#[cfg(feature = "alloc")]
+fn json_option(output: &mut String, value: Option<&str>) {
+    match value {
+        Some(v) => json_string(output, v),
+        None => output.push_str("null"),
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn json_string(output: &mut String, value: &str) {
+    use core::fmt::Write;
+
+    output.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if c.is_control() => {
+                // Writing to a `String` cannot fail
+                let _ = write!(output, "\\u{:04x}", c as u32);
+            }
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+}
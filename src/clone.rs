@@ -0,0 +1,48 @@
+//! Support for cloning errors that use `#[snafu(clone)]`.
+//!
+//! Fields are cloned through these functions instead of
+//! [`Clone::clone`] so that a field that cannot be cloned, such as an
+//! [`std::io::Error`][] source or a standard library backtrace, is
+//! reported with a message that explains how to store it instead.
+
+#[cfg_attr(
+    feature = "rust_1_81",
+    diagnostic::on_unimplemented(
+        message = "the source `{Self}` cannot be cloned by `#[snafu(clone)]`; declare the field as `snafu::Shared<{Self}>`",
+    )
+)]
+pub trait CloneableSource: Clone {}
+
+impl<T: Clone> CloneableSource for T {}
+
+#[cfg_attr(
+    feature = "rust_1_81",
+    diagnostic::on_unimplemented(
+        message = "the backtrace `{Self}` cannot be cloned by `#[snafu(clone)]`; declare the field as `Arc<{Self}>`",
+    )
+)]
+pub trait CloneableBacktrace: Clone {}
+
+impl<T: Clone> CloneableBacktrace for T {}
+
+#[cfg_attr(
+    feature = "rust_1_81",
+    diagnostic::on_unimplemented(
+        message = "the field `{Self}` cannot be cloned by `#[snafu(clone)]`",
+    )
+)]
+pub trait CloneableField: Clone {}
+
+impl<T: Clone> CloneableField for T {}
+
+pub fn clone_source<T: CloneableSource>(source: &T) -> T {
+    source.clone()
+}
+
+pub fn clone_backtrace<T: CloneableBacktrace>(backtrace: &T) -> T {
+    backtrace.clone()
+}
+
+pub fn clone_field<T: CloneableField>(field: &T) -> T {
+    field.clone()
+}
@@ -0,0 +1,52 @@
+//! Supporting types for builder-style context selectors.
+//!
+//! These types are used by context selectors generated with
+//! [`#[snafu(context(builder))]`][crate::Snafu#builder-style-context-selectors].
+//! You should not need to name them directly, but they may appear in
+//! compiler diagnostics, such as when a required field has not been
+//! set.
+
+/// A field of a builder-style context selector that has not been
+/// set.
+///
+/// A required field that is still `Missing` does not implement
+/// [`Into`] for the field type, so the context selector cannot be
+/// used until the field's setter has been called.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Missing;
+
+/// A defaulted field of a builder-style context selector that has
+/// been set.
+#[derive(Debug, Copy, Clone)]
+pub struct Provided<T>(pub T);
+
+/// Resolves the value of a defaulted field of a builder-style context
+/// selector.
+pub trait DefaultedField<T> {
+    /// Returns the provided value or, if none was provided, the
+    /// result of calling `default`.
+    fn resolve<F>(self, default: F) -> T
+    where
+        F: FnOnce() -> T;
+}
+
+impl<T> DefaultedField<T> for Missing {
+    fn resolve<F>(self, default: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        default()
+    }
+}
+
+impl<T, U> DefaultedField<T> for Provided<U>
+where
+    U: Into<T>,
+{
+    fn resolve<F>(self, _default: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        self.0.into()
+    }
+}
@@ -0,0 +1,79 @@
+//! Choosing the process exit code for an error.
+//!
+//! Adding `#[snafu(exit_code = ...)]` to a variant (or to a struct)
+//! implements [`ExitCodeProvider`][] for the error. The value may be
+//! anything that converts into an [`ExitCode`][], such as a `u8`.
+//! Variants without an exit code use the exit code of their source,
+//! when the source also implements [`ExitCodeProvider`][].
+//!
+//! A [`Report`][crate::Report] created by the
+//! [`snafu::report`][macro@crate::report] procedural macro, or one
+//! that uses [`Report::with_exit_code`][crate::Report::with_exit_code],
+//! exits with the error's exit code when returned from `main`.
+//!
+//! ```rust,no_run
+//! use snafu::prelude::*;
+//!
+//! #[derive(Debug, Snafu)]
+//! enum Error {
+//!     #[snafu(display("The configuration is invalid"), exit_code = 78)]
+//!     Config,
+//!
+//!     #[snafu(display("Something else went wrong"))]
+//!     Other,
+//! }
+//!
+//! #[snafu::report]
+//! fn main() -> Result<(), Error> {
+//!     // Exits with code 78
+//!     ConfigSnafu.fail()
+//! }
+//! ```
+//!
+//! Errors that do not provide an exit code fall back to the exit code
+//! of their [`Severity`][crate::Severity], if any, and then to
+//! [`ExitCode::FAILURE`][].
+
+pub use std::process::ExitCode;
+
+/// Provides the exit code to use when the error terminates the
+/// process.
+///
+/// This is implemented by `#[snafu(exit_code = ...)]`.
+pub trait ExitCodeProvider {
+    /// The exit code for this error, if it has one.
+    fn exit_code(&self) -> Option<ExitCode>;
+}
+
+// Variants without an exit code ask their source, when the source
+// implements `ExitCodeProvider`. The choice is made by method
+// resolution: the preferred implementation is on `&__SourceRef` and
+// the fallback is on `__SourceRef`.
+
+#[doc(hidden)]
+pub struct __SourceRef<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait __IsExitCodeProvider {
+    fn __snafu_exit_code(&self) -> Option<ExitCode>;
+}
+
+impl<T> __IsExitCodeProvider for &__SourceRef<'_, T>
+where
+    T: ExitCodeProvider + ?Sized,
+{
+    fn __snafu_exit_code(&self) -> Option<ExitCode> {
+        self.0.exit_code()
+    }
+}
+
+#[doc(hidden)]
+pub trait __NotExitCodeProvider {
+    fn __snafu_exit_code(&self) -> Option<ExitCode>;
+}
+
+impl<T: ?Sized> __NotExitCodeProvider for __SourceRef<'_, T> {
+    fn __snafu_exit_code(&self) -> Option<ExitCode> {
+        None
+    }
+}
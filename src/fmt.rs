@@ -0,0 +1,267 @@
+//! Helpers for rendering fields in display messages.
+//!
+//! Some field values need a little work to be readable, such as
+//! converting a byte count to a larger unit. Instead of writing a
+//! wrapper type, give the field a formatting function with
+//! `#[snafu(display_with = ...)]`. The function is used whenever the
+//! field is referred to by name in the display format:
+//!
+//! ```rust
+//! use snafu::prelude::*;
+//! use std::time::Duration;
+//!
+//! #[derive(Debug, Snafu)]
+//! #[snafu(display("Uploading {size} took {elapsed}"))]
+//! struct SlowUploadError {
+//!     #[snafu(display_with = snafu::fmt::bytes)]
+//!     size: u64,
+//!     #[snafu(display_with = snafu::fmt::duration)]
+//!     elapsed: Duration,
+//! }
+//!
+//! let e = SlowUploadSnafu {
+//!     size: 1536u64,
+//!     elapsed: Duration::from_millis(90_500),
+//! }
+//! .build();
+//!
+//! assert_eq!(e.to_string(), "Uploading 1.5 KiB took 1m 30.5s");
+//! ```
+//!
+//! A function can also be chosen for a single use of a field with
+//! `{field:with(function)}`:
+//!
+//! ```rust
+//! use snafu::prelude::*;
+//!
+//! #[derive(Debug, Snafu)]
+//! #[snafu(display("Expected one of {expected:with(snafu::fmt::list)}, got {actual}"))]
+//! struct UnexpectedTokenError {
+//!     expected: Vec<char>,
+//!     actual: char,
+//! }
+//!
+//! let e = UnexpectedTokenSnafu {
+//!     expected: vec!['(', '['],
+//!     actual: '{',
+//! }
+//! .build();
+//!
+//! assert_eq!(e.to_string(), "Expected one of (, [, got {");
+//! ```
+//!
+//! Any function or closure with the signature `fn(&T, &mut
+//! Formatter<'_>) -> fmt::Result` may be used, where `T` is the type
+//! of the field.
+
+use core::{fmt, time::Duration};
+
+/// Displays a value using a formatting function.
+///
+/// Created by [`with`][].
+pub struct With<'a, T: ?Sized, F> {
+    value: &'a T,
+    f: F,
+}
+
+/// Displays `value` by calling `f`.
+///
+/// ```rust
+/// let size = snafu::fmt::with(&2048u64, snafu::fmt::bytes);
+/// assert_eq!(size.to_string(), "2.0 KiB");
+/// ```
+pub fn with<T, F>(value: &T, f: F) -> With<'_, T, F>
+where
+    T: ?Sized,
+    F: Fn(&T, &mut fmt::Formatter<'_>) -> fmt::Result,
+{
+    With { value, f }
+}
+
+impl<T, F> fmt::Display for With<'_, T, F>
+where
+    T: ?Sized,
+    F: Fn(&T, &mut fmt::Formatter<'_>) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.f)(self.value, f)
+    }
+}
+
+/// A number of bytes that can be displayed by [`bytes`][].
+pub trait ByteCount: Copy {
+    #[doc(hidden)]
+    fn __snafu_byte_count(self) -> u64;
+}
+
+macro_rules! impl_byte_count {
+    ($($ty:ty),*) => {
+        $(
+            impl ByteCount for $ty {
+                fn __snafu_byte_count(self) -> u64 {
+                    self as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_byte_count![u8, u16, u32, u64, usize];
+
+/// Displays a number of bytes using binary units.
+///
+/// ```rust
+/// # use snafu::fmt::{bytes, with};
+/// assert_eq!(with(&512u32, bytes).to_string(), "512 B");
+/// assert_eq!(with(&1536usize, bytes).to_string(), "1.5 KiB");
+/// assert_eq!(with(&(3u64 << 30), bytes).to_string(), "3.0 GiB");
+/// assert_eq!(with(&1048575u32, bytes).to_string(), "1.0 MiB");
+/// ```
+pub fn bytes<T>(value: &T, f: &mut fmt::Formatter<'_>) -> fmt::Result
+where
+    T: ByteCount,
+{
+    const UNITS: &[&str] = &["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+    let value = value.__snafu_byte_count();
+
+    if value < 1024 {
+        return write!(f, "{} B", value);
+    }
+
+    let mut scaled = value as f64 / 1024.0;
+    let mut units = UNITS.iter().peekable();
+
+    // Values that would be rounded up to 1024.0 use the next unit
+    while let Some(unit) = units.next() {
+        if scaled < 1023.95 || units.peek().is_none() {
+            return write!(f, "{:.1} {}", scaled, unit);
+        }
+        scaled /= 1024.0;
+    }
+
+    Ok(())
+}
+
+/// Displays a duration using the largest units that apply.
+///
+/// The value is truncated, not rounded, to the smallest unit shown.
+/// Durations of a second or more are shown to the millisecond, so
+/// 1.0005s is shown as `1s`.
+///
+/// ```rust
+/// # use snafu::fmt::{duration, with};
+/// # use std::time::Duration;
+/// assert_eq!(with(&Duration::from_micros(250), duration).to_string(), "250µs");
+/// assert_eq!(with(&Duration::from_millis(1500), duration).to_string(), "1.5s");
+/// assert_eq!(with(&Duration::from_micros(1_000_500), duration).to_string(), "1s");
+/// assert_eq!(with(&Duration::from_secs(3725), duration).to_string(), "1h 2m 5s");
+/// ```
+pub fn duration(value: &Duration, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let secs = value.as_secs();
+    let nanos = value.subsec_nanos();
+
+    if secs == 0 {
+        return if nanos >= 1_000_000 {
+            write!(f, "{}ms", nanos / 1_000_000)
+        } else if nanos >= 1_000 {
+            write!(f, "{}µs", nanos / 1_000)
+        } else {
+            write!(f, "{}ns", nanos)
+        };
+    }
+
+    let (hours, minutes, secs) = (secs / 3600, secs / 60 % 60, secs % 60);
+
+    if hours > 0 {
+        write!(f, "{}h ", hours)?;
+    }
+    if hours > 0 || minutes > 0 {
+        write!(f, "{}m ", minutes)?;
+    }
+
+    write!(f, "{}", secs)?;
+
+    // Trailing zeros are dropped: 1.500s is shown as 1.5s
+    let mut millis = nanos / 1_000_000;
+    if millis > 0 {
+        let mut width = 3;
+        while millis % 10 == 0 {
+            millis /= 10;
+            width -= 1;
+        }
+        write!(f, ".{:0width$}", millis, width = width)?;
+    }
+
+    f.write_str("s")
+}
+
+/// A collection that can be displayed by [`list`][].
+pub trait List {
+    #[doc(hidden)]
+    fn __snafu_fmt_list(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+macro_rules! impl_list {
+    ($(#[$meta:meta])* [$($generics:tt)*] $ty:ty) => {
+        $(#[$meta])*
+        impl<$($generics)*> List for $ty {
+            fn __snafu_fmt_list(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write_list(self.iter(), f)
+            }
+        }
+    };
+}
+
+impl_list!([T: fmt::Display] [T]);
+impl_list!([T: fmt::Display, const N: usize] [T; N]);
+impl_list!(#[cfg(feature = "alloc")] [T: fmt::Display] alloc::vec::Vec<T>);
+impl_list!(#[cfg(feature = "alloc")] [T: fmt::Display] alloc::collections::VecDeque<T>);
+impl_list!(#[cfg(feature = "alloc")] [T: fmt::Display] alloc::collections::BTreeSet<T>);
+impl_list!(#[cfg(feature = "std")] [T: fmt::Display, S] std::collections::HashSet<T, S>);
+
+fn write_list<I>(mut items: I, f: &mut fmt::Formatter<'_>) -> fmt::Result
+where
+    I: Iterator,
+    I::Item: fmt::Display,
+{
+    if let Some(item) = items.next() {
+        write!(f, "{}", item)?;
+    }
+
+    for item in items {
+        write!(f, ", {}", item)?;
+    }
+
+    Ok(())
+}
+
+/// Displays each item, separated by commas.
+///
+/// ```rust
+/// # use snafu::fmt::{list, with};
+/// assert_eq!(with(&["a", "b", "c"], list).to_string(), "a, b, c");
+/// assert_eq!(with(&Vec::<u8>::new(), list).to_string(), "");
+/// ```
+pub fn list<T>(value: &T, f: &mut fmt::Formatter<'_>) -> fmt::Result
+where
+    T: ?Sized + List,
+{
+    value.__snafu_fmt_list(f)
+}
+
+/// Displays a filesystem path.
+///
+/// ```rust
+/// # use snafu::fmt::{path, with};
+/// # use std::path::PathBuf;
+/// let config = PathBuf::from("/etc/app/config.toml");
+/// assert_eq!(with(&config, path).to_string(), "/etc/app/config.toml");
+/// ```
+#[cfg(feature = "std")]
+pub fn path<T>(value: &T, f: &mut fmt::Formatter<'_>) -> fmt::Result
+where
+    T: ?Sized + AsRef<std::path::Path>,
+{
+    fmt::Display::fmt(&value.as_ref().display(), f)
+}
@@ -0,0 +1,316 @@
+//! Localized display messages.
+//!
+//! Errors can give each display message a stable key with
+//! `#[snafu(display(key = "..."))]`. When the error is rendered by a
+//! [`Report`][crate::Report] that has been given a [`Catalog`][] via
+//! [`Report::with_catalog`][crate::Report::with_catalog], the catalog
+//! is asked to produce the message for that key. If the catalog does
+//! not know the key, the literal `display` text is used instead.
+//!
+//! ```rust
+//! # #[cfg(feature = "std")] {
+//! use snafu::{i18n::Args, prelude::*, Report};
+//!
+//! #[derive(Debug, Snafu)]
+//! #[snafu(display(key = "file-not-found", "Could not find {name}"))]
+//! struct FileNotFoundError {
+//!     name: String,
+//! }
+//!
+//! fn german(key: &str, args: &Args<'_>) -> Option<String> {
+//!     match key {
+//!         "file-not-found" => Some(args.interpolate("Datei {name} nicht gefunden")),
+//!         _ => None,
+//!     }
+//! }
+//!
+//! let error = FileNotFoundError { name: "config.toml".into() };
+//! let report = Report::from_error(error).with_catalog(german);
+//!
+//! assert_eq!(report.to_string(), "Datei config.toml nicht gefunden\n");
+//! # }
+//! ```
+//!
+//! The catalog is only consulted while a [`Report`][crate::Report]
+//! with a catalog is being displayed, which requires the `std`
+//! feature. In every other case, errors display their literal text.
+
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+#[cfg(feature = "std")]
+use alloc::sync::Arc;
+
+/// Resolves the display message for a key.
+///
+/// Implemented for all closures with the signature `Fn(&str,
+/// &Args<'_>) -> Option<String>`.
+#[cfg(feature = "alloc")]
+pub trait Catalog {
+    /// Returns the localized message for `key`, or `None` if the
+    /// catalog does not have an entry for it.
+    ///
+    /// `args` contains the named fields of the error.
+    fn message(&self, key: &str, args: &Args<'_>) -> Option<String>;
+}
+
+#[cfg(feature = "alloc")]
+impl<F> Catalog for F
+where
+    F: Fn(&str, &Args<'_>) -> Option<String>,
+{
+    fn message(&self, key: &str, args: &Args<'_>) -> Option<String> {
+        self(key, args)
+    }
+}
+
+/// The named fields of an error, available to a [`Catalog`][].
+///
+/// Source and backtrace fields are not included.
+#[derive(Copy, Clone)]
+pub struct Args<'a>(&'a [Arg<'a>]);
+
+impl<'a> Args<'a> {
+    /// Returns the field with the given name.
+    pub fn get(&self, name: &str) -> Option<&Arg<'a>> {
+        self.0.iter().find(|a| a.name == name)
+    }
+
+    /// Iterates over all of the fields.
+    pub fn iter(&self) -> core::slice::Iter<'_, Arg<'a>> {
+        self.0.iter()
+    }
+
+    /// Replaces each `{name}` in the template with the value of the
+    /// field with that name. `{{` and `}}` produce literal braces and
+    /// unknown names are left untouched.
+    #[cfg(feature = "alloc")]
+    pub fn interpolate(&self, template: &str) -> String {
+        use core::fmt::Write;
+
+        let mut output = String::with_capacity(template.len());
+        let mut rest = template;
+
+        while let Some(idx) = rest.find(['{', '}']) {
+            let (head, tail) = rest.split_at(idx);
+            output.push_str(head);
+
+            if tail.starts_with("{{") || tail.starts_with("}}") {
+                output.push_str(&tail[..1]);
+                rest = &tail[2..];
+                continue;
+            }
+
+            let arg = tail
+                .strip_prefix('{')
+                .and_then(|t| t.find('}').map(|end| (&t[..end], &t[end + 1..])))
+                .and_then(|(name, after)| self.get(name).map(|a| (a, after)));
+
+            match arg {
+                Some((arg, after)) => {
+                    // Writing to a `String` cannot fail
+                    let _ = write!(output, "{}", arg);
+                    rest = after;
+                }
+                None => {
+                    output.push_str(&tail[..1]);
+                    rest = &tail[1..];
+                }
+            }
+        }
+
+        output.push_str(rest);
+        output
+    }
+}
+
+impl<'a, 'b> IntoIterator for &'b Args<'a> {
+    type Item = &'b Arg<'a>;
+    type IntoIter = core::slice::Iter<'b, Arg<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl fmt::Debug for Args<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.0.iter().map(|a| (a.name, a.value)))
+            .finish()
+    }
+}
+
+/// A single named field of an error.
+///
+/// Formatting an `Arg` with [`Display`][fmt::Display] uses the
+/// field's `Display` implementation if it has one, otherwise its
+/// `Debug` implementation.
+#[derive(Copy, Clone)]
+pub struct Arg<'a> {
+    name: &'static str,
+    value: ArgValue<'a>,
+}
+
+impl Arg<'_> {
+    /// The name of the field.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl fmt::Display for Arg<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.value, f)
+    }
+}
+
+impl fmt::Debug for Arg<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Arg")
+            .field("name", &self.name)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+#[derive(Copy, Clone)]
+enum ArgValue<'a> {
+    Display(&'a dyn fmt::Display),
+    Debug(&'a dyn fmt::Debug),
+}
+
+impl fmt::Display for ArgValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArgValue::Display(v) => v.fmt(f),
+            ArgValue::Debug(v) => v.fmt(f),
+        }
+    }
+}
+
+impl fmt::Debug for ArgValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\"", self)
+    }
+}
+
+// Fields are converted to an `Arg` using their `Display`
+// implementation when they have one, falling back to `Debug`. The
+// choice is made by method resolution: the preferred implementation is
+// on `&ArgRef` and the fallback is on `ArgRef`, so calling
+// `(&&ArgRef(v)).__snafu_arg(..)` picks the first one that applies.
+
+#[doc(hidden)]
+pub struct __ArgRef<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait __ByDisplay<'a> {
+    fn __snafu_arg(&self, name: &'static str) -> Arg<'a>;
+}
+
+impl<'a, T> __ByDisplay<'a> for &__ArgRef<'a, T>
+where
+    T: fmt::Display,
+{
+    fn __snafu_arg(&self, name: &'static str) -> Arg<'a> {
+        let value = ArgValue::Display(self.0);
+        Arg { name, value }
+    }
+}
+
+#[doc(hidden)]
+pub trait __ByDebug<'a> {
+    fn __snafu_arg(&self, name: &'static str) -> Arg<'a>;
+}
+
+impl<'a, T> __ByDebug<'a> for __ArgRef<'a, T>
+where
+    T: fmt::Debug,
+{
+    fn __snafu_arg(&self, name: &'static str) -> Arg<'a> {
+        let value = ArgValue::Debug(self.0);
+        Arg { name, value }
+    }
+}
+
+/// Writes the localized message for `key` if a catalog is active and
+/// has an entry for the key.
+#[doc(hidden)]
+pub fn __localize(key: &str, args: &[Arg<'_>], f: &mut fmt::Formatter<'_>) -> Option<fmt::Result> {
+    #[cfg(feature = "std")]
+    {
+        let catalog = ACTIVE.with(|active| active.borrow().clone())?;
+        let message = catalog.message(key, &Args(args))?;
+        Some(f.write_str(&message))
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        let _ = (key, args, f);
+        None
+    }
+}
+
+#[cfg(feature = "std")]
+type SharedCatalog = Arc<dyn Catalog + Send + Sync>;
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static ACTIVE: core::cell::RefCell<Option<SharedCatalog>> = const { core::cell::RefCell::new(None) };
+}
+
+/// The catalog attached to a [`Report`][crate::Report], if any.
+#[derive(Clone)]
+pub(crate) struct ReportCatalog {
+    #[cfg(feature = "std")]
+    catalog: Option<SharedCatalog>,
+}
+
+impl ReportCatalog {
+    pub(crate) const fn none() -> Self {
+        Self {
+            #[cfg(feature = "std")]
+            catalog: None,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn new(catalog: impl Catalog + Send + Sync + 'static) -> Self {
+        Self {
+            catalog: Some(Arc::new(catalog)),
+        }
+    }
+
+    /// Runs the closure with this catalog as the active one on the
+    /// current thread.
+    pub(crate) fn scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        #[cfg(feature = "std")]
+        {
+            let catalog = match &self.catalog {
+                Some(c) => c.clone(),
+                None => return f(),
+            };
+
+            struct Restore(Option<SharedCatalog>);
+
+            impl Drop for Restore {
+                fn drop(&mut self) {
+                    let previous = self.0.take();
+                    ACTIVE.with(|active| *active.borrow_mut() = previous);
+                }
+            }
+
+            let previous = ACTIVE.with(|active| active.borrow_mut().replace(catalog));
+            let _restore = Restore(previous);
+            f()
+        }
+
+        #[cfg(not(feature = "std"))]
+        {
+            f()
+        }
+    }
+}
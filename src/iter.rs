@@ -0,0 +1,361 @@
+//! Additions to the [`Iterator`] trait for iterators of [`Result`]s.
+
+use crate::{Error, ErrorCompat, IntoError};
+use core::marker::PhantomData;
+
+#[cfg(any(feature = "alloc", test))]
+use alloc::string::String;
+
+#[cfg(any(feature = "alloc", test))]
+use crate::FromString;
+
+/// Additions to [`Iterator`]s that yield [`Result`]s.
+pub trait TryIteratorExt<T, E>: Iterator<Item = Result<T, E>> + Sized {
+    /// Extend each error yielded by an [`Iterator`] with additional
+    /// context-sensitive information.
+    ///
+    /// ```rust
+    /// use snafu::prelude::*;
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     Authenticating {
+    ///         user_name: String,
+    ///         user_id: i32,
+    ///         source: ApiError,
+    ///     },
+    /// }
+    ///
+    /// fn example() -> impl Iterator<Item = Result<i32, Error>> {
+    ///     stock_prices().context(AuthenticatingSnafu {
+    ///         user_name: "admin",
+    ///         user_id: 42,
+    ///     })
+    /// }
+    ///
+    /// # type ApiError = Box<dyn std::error::Error>;
+    /// fn stock_prices() -> impl Iterator<Item = Result<i32, ApiError>> {
+    ///     /* ... */
+    /// # std::iter::empty()
+    /// }
+    /// ```
+    ///
+    /// Note that the context selector will call [`Into::into`] on
+    /// each field, so the types are not required to exactly match.
+    fn context<C, E2>(self, context: C) -> Context<Self, C, E2>
+    where
+        C: IntoError<E2, Source = E> + Clone,
+        E2: Error + ErrorCompat;
+
+    /// Extend each error yielded by an [`Iterator`] with
+    /// lazily-generated context-sensitive information.
+    ///
+    /// ```rust
+    /// use snafu::prelude::*;
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     Authenticating {
+    ///         user_name: String,
+    ///         user_id: i32,
+    ///         source: ApiError,
+    ///     },
+    /// }
+    ///
+    /// fn example() -> impl Iterator<Item = Result<i32, Error>> {
+    ///     stock_prices().with_context(|_| AuthenticatingSnafu {
+    ///         user_name: "admin",
+    ///         user_id: 42,
+    ///     })
+    /// }
+    ///
+    /// # type ApiError = Box<dyn std::error::Error>;
+    /// fn stock_prices() -> impl Iterator<Item = Result<i32, ApiError>> {
+    ///     /* ... */
+    /// # std::iter::empty()
+    /// }
+    /// ```
+    ///
+    /// Note that this *may not* be needed in many cases because the
+    /// context selector will call [`Into::into`] on each field.
+    fn with_context<F, C, E2>(self, context: F) -> WithContext<Self, F, E2>
+    where
+        F: FnMut(&mut E) -> C,
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat;
+
+    /// Extend each error yielded by an [`Iterator`] with information
+    /// from a string.
+    ///
+    /// The target error type must implement [`FromString`] by using
+    /// the
+    /// [`#[snafu(whatever)]`][crate::Snafu#controlling-stringly-typed-errors]
+    /// attribute. The premade [`Whatever`](crate::Whatever) type is also available.
+    ///
+    /// In many cases, you will want to use
+    /// [`with_whatever_context`][Self::with_whatever_context] instead
+    /// as it is only called in case of error. This method is best
+    /// suited for when you have a string literal.
+    ///
+    /// ```rust
+    /// use snafu::{prelude::*, Whatever};
+    ///
+    /// fn example() -> impl Iterator<Item = Result<i32, Whatever>> {
+    ///     stock_prices().whatever_context("Couldn't get stock prices")
+    /// }
+    ///
+    /// # type ApiError = Box<dyn std::error::Error + Send + Sync>;
+    /// fn stock_prices() -> impl Iterator<Item = Result<i32, ApiError>> {
+    ///     /* ... */
+    /// # std::iter::empty()
+    /// }
+    /// ```
+    #[cfg(any(feature = "alloc", test))]
+    fn whatever_context<S, E2>(self, context: S) -> WhateverContext<Self, S, E2>
+    where
+        S: Into<String>,
+        E2: FromString;
+
+    /// Extend each error yielded by an [`Iterator`] with information
+    /// from a lazily-generated string.
+    ///
+    /// The target error type must implement [`FromString`] by using
+    /// the
+    /// [`#[snafu(whatever)]`][crate::Snafu#controlling-stringly-typed-errors]
+    /// attribute. The premade [`Whatever`](crate::Whatever) type is also available.
+    ///
+    /// ```rust
+    /// use snafu::{prelude::*, Whatever};
+    ///
+    /// fn example(lines: &[&str]) -> Result<Vec<u8>, Whatever> {
+    ///     lines
+    ///         .iter()
+    ///         .map(|line| line.parse::<u8>())
+    ///         .with_whatever_context(|e| format!("Couldn't parse a line: {e}"))
+    ///         .collect()
+    /// }
+    ///
+    /// let err = example(&["1", "two", "3"]).unwrap_err();
+    /// assert_eq!(
+    ///     "Couldn't parse a line: invalid digit found in string",
+    ///     err.to_string(),
+    /// );
+    /// ```
+    #[cfg(any(feature = "alloc", test))]
+    fn with_whatever_context<F, S, E2>(self, context: F) -> WithWhateverContext<Self, F, E2>
+    where
+        F: FnMut(&mut E) -> S,
+        S: Into<String>,
+        E2: FromString;
+}
+
+impl<I, T, E> TryIteratorExt<T, E> for I
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    fn context<C, E2>(self, context: C) -> Context<Self, C, E2>
+    where
+        C: IntoError<E2, Source = E> + Clone,
+        E2: Error + ErrorCompat,
+    {
+        Context {
+            inner: self,
+            context,
+            _e: PhantomData,
+        }
+    }
+
+    fn with_context<F, C, E2>(self, context: F) -> WithContext<Self, F, E2>
+    where
+        F: FnMut(&mut E) -> C,
+        C: IntoError<E2, Source = E>,
+        E2: Error + ErrorCompat,
+    {
+        WithContext {
+            inner: self,
+            context,
+            _e: PhantomData,
+        }
+    }
+
+    #[cfg(any(feature = "alloc", test))]
+    fn whatever_context<S, E2>(self, context: S) -> WhateverContext<Self, S, E2>
+    where
+        S: Into<String>,
+        E2: FromString,
+    {
+        WhateverContext {
+            inner: self,
+            context,
+            _e: PhantomData,
+        }
+    }
+
+    #[cfg(any(feature = "alloc", test))]
+    fn with_whatever_context<F, S, E2>(self, context: F) -> WithWhateverContext<Self, F, E2>
+    where
+        F: FnMut(&mut E) -> S,
+        S: Into<String>,
+        E2: FromString,
+    {
+        WithWhateverContext {
+            inner: self,
+            context,
+            _e: PhantomData,
+        }
+    }
+}
+
+/// Iterator for the [`context`](TryIteratorExt::context) combinator.
+///
+/// See the [`TryIteratorExt::context`] method for more details.
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Context<I, C, E> {
+    inner: I,
+    context: C,
+    _e: PhantomData<E>,
+}
+
+impl<I, T, E, C, E2> Iterator for Context<I, C, E2>
+where
+    I: Iterator<Item = Result<T, E>>,
+    C: IntoError<E2, Source = E> + Clone,
+    E2: Error + ErrorCompat,
+{
+    type Item = Result<T, E2>;
+
+    #[track_caller]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.inner.next()? {
+            Ok(v) => Some(Ok(v)),
+            Err(error) => {
+                let error = self.context.clone().into_error(error);
+                Some(Err(error))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Iterator for the [`with_context`](TryIteratorExt::with_context) combinator.
+///
+/// See the [`TryIteratorExt::with_context`] method for more details.
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct WithContext<I, F, E> {
+    inner: I,
+    context: F,
+    _e: PhantomData<E>,
+}
+
+impl<I, T, E, F, C, E2> Iterator for WithContext<I, F, E2>
+where
+    I: Iterator<Item = Result<T, E>>,
+    F: FnMut(&mut E) -> C,
+    C: IntoError<E2, Source = E>,
+    E2: Error + ErrorCompat,
+{
+    type Item = Result<T, E2>;
+
+    #[track_caller]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.inner.next()? {
+            Ok(v) => Some(Ok(v)),
+            Err(mut error) => {
+                let error = (self.context)(&mut error).into_error(error);
+                Some(Err(error))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Iterator for the
+/// [`whatever_context`](TryIteratorExt::whatever_context) combinator.
+///
+/// See the [`TryIteratorExt::whatever_context`] method for more
+/// details.
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[cfg(any(feature = "alloc", test))]
+pub struct WhateverContext<I, S, E> {
+    inner: I,
+    context: S,
+    _e: PhantomData<E>,
+}
+
+#[cfg(any(feature = "alloc", test))]
+impl<I, T, E, S, E2> Iterator for WhateverContext<I, S, E2>
+where
+    I: Iterator<Item = Result<T, E>>,
+    S: Into<String> + Clone,
+    E2: FromString,
+    E: Into<E2::Source>,
+{
+    type Item = Result<T, E2>;
+
+    #[track_caller]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.inner.next()? {
+            Ok(v) => Some(Ok(v)),
+            Err(error) => {
+                let error = E2::with_source(error.into(), self.context.clone().into());
+                Some(Err(error))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Iterator for the
+/// [`with_whatever_context`](TryIteratorExt::with_whatever_context)
+/// combinator.
+///
+/// See the [`TryIteratorExt::with_whatever_context`] method for more
+/// details.
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[cfg(any(feature = "alloc", test))]
+pub struct WithWhateverContext<I, F, E> {
+    inner: I,
+    context: F,
+    _e: PhantomData<E>,
+}
+
+#[cfg(any(feature = "alloc", test))]
+impl<I, T, E, F, S, E2> Iterator for WithWhateverContext<I, F, E2>
+where
+    I: Iterator<Item = Result<T, E>>,
+    F: FnMut(&mut E) -> S,
+    S: Into<String>,
+    E2: FromString,
+    E: Into<E2::Source>,
+{
+    type Item = Result<T, E2>;
+
+    #[track_caller]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.inner.next()? {
+            Ok(v) => Some(Ok(v)),
+            Err(mut error) => {
+                let context = (self.context)(&mut error);
+                let error = E2::with_source(error.into(), context.into());
+                Some(Err(error))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
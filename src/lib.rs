@@ -26,6 +26,7 @@
 //! - Extension traits for
 //!   - [`Results`](ResultExt)
 //!   - [`Options`](OptionExt)
+//!   - [`Iterators`](iter::TryIteratorExt)
 #![cfg_attr(feature = "futures", doc = "   - [`Futures`](futures::TryFutureExt)")]
 #![cfg_attr(feature = "futures", doc = "   - [`Streams`](futures::TryStreamExt)")]
 //! - [Error reporting](#reporting)
@@ -277,7 +278,10 @@ pub mod prelude {
     //! snafu::prelude::*` to your code to quickly get started with
     //! SNAFU.
 
-    pub use crate::{ensure, OptionExt as _, ResultExt as _};
+    pub use crate::{
+        ensure, ensure_eq, ensure_ne, ensure_ok, ensure_some, iter::TryIteratorExt as _,
+        BoolExt as _, OptionExt as _, ResultExt as _,
+    };
 
     // https://github.com/rust-lang/rust/issues/89020
     #[doc = include_str!("Snafu.md")]
@@ -286,7 +290,10 @@ pub mod prelude {
     pub use snafu_derive::Snafu;
 
     #[cfg(any(feature = "alloc", test))]
-    pub use crate::{ensure_whatever, whatever};
+    pub use crate::{
+        ensure_whatever, ensure_whatever_eq, ensure_whatever_ne, ensure_whatever_ok,
+        ensure_whatever_some, whatever,
+    };
 
     #[cfg(feature = "futures")]
     pub use crate::futures::{TryFutureExt as _, TryStreamExt as _};
@@ -309,26 +316,69 @@ pub use backtrace_impl::*;
 #[cfg(any(feature = "std", test))]
 mod once_bool;
 
+#[cfg(any(feature = "std", feature = "backtraces-impl-backtrace-crate", test))]
+mod once_cell;
+
 #[cfg(feature = "futures")]
 pub mod futures;
 
+pub mod iter;
+
+pub mod catalog;
+pub use crate::catalog::{ErrorDescriptor, FieldDescriptor};
+
+pub mod context_builder;
+
+pub mod i18n;
+
+pub mod fmt;
+
+#[cfg(feature = "std")]
+pub mod backtrace;
+
+#[cfg(feature = "std")]
+pub mod exit_code;
+
+#[cfg(feature = "std")]
+pub mod registry;
+
 mod error_chain;
 pub use crate::error_chain::*;
 
+mod location_trace;
+pub use crate::location_trace::LocationTrace;
+
+mod severity;
+pub use crate::severity::Severity;
+
 mod report;
 #[cfg(feature = "alloc")]
 pub use report::CleanedErrorText;
-pub use report::{Report, __InternalExtractErrorType};
+pub use report::{
+    __InternalErrorCompat, __InternalExitCode, __InternalExtractErrorType,
+    __InternalWithErrorCompat, __InternalWithExitCode, __InternalWithoutErrorCompat,
+    __InternalWithoutExitCode, Report,
+};
 
 #[doc = include_str!("Snafu.md")]
 #[doc(alias(
     "backtrace",
+    "catalog",
+    "clone",
     "context",
     "crate_root",
+    "default",
     "display",
+    "display_source",
+    "display_with",
+    "exit_code",
+    "from",
     "implicit",
     "module",
+    "opaque",
+    "partial_eq",
     "provide",
+    "severity",
     "source",
     "transparent",
     "visibility",
@@ -339,6 +389,9 @@ pub use snafu_derive::Snafu;
 #[doc = include_str!("report.md")]
 pub use snafu_derive::report;
 
+#[doc = include_str!("wrap.md")]
+pub use snafu_derive::wrap;
+
 macro_rules! generate_guide {
     (pub mod $name:ident { $($children:tt)* } $($rest:tt)*) => {
         generate_guide!(@gen ".", pub mod $name { $($children)* } $($rest)*);
@@ -425,6 +478,23 @@ mod whatever;
 #[cfg(any(feature = "alloc", test))]
 pub use whatever::*;
 
+#[cfg(all(any(feature = "alloc", test), target_has_atomic = "ptr"))]
+mod shared;
+#[cfg(all(any(feature = "alloc", test), target_has_atomic = "ptr"))]
+pub use shared::Shared;
+
+#[cfg(any(feature = "alloc", test))]
+#[doc(hidden)]
+pub use alloc::boxed::Box as __Box;
+
+#[doc(hidden)]
+#[path = "partial_eq.rs"]
+pub mod __partial_eq;
+
+#[doc(hidden)]
+#[path = "clone.rs"]
+pub mod __clone;
+
 /// Ensure a condition is true. If it is not, return from the function
 /// with an error.
 ///
@@ -456,6 +526,198 @@ macro_rules! ensure {
     };
 }
 
+/// Ensure two values are equal. If they are not, return from the
+/// function with an error.
+///
+/// Both values must implement [`PartialEq`][] with each other. The
+/// values are compared by reference and are not consumed.
+///
+/// ## Examples
+///
+/// ```rust
+/// use snafu::prelude::*;
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     WrongVersion { version: u8 },
+/// }
+///
+/// fn example(version: u8) -> Result<(), Error> {
+///     ensure_eq!(version, 2, WrongVersionSnafu { version });
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Accessing the compared values
+///
+/// When the context selector is written as a closure with two
+/// parameters, the parameters are bound to references to the left and
+/// right values, respectively. The closure body must be the context
+/// selector and is only evaluated when the values are not equal.
+///
+/// ```rust
+/// use snafu::prelude::*;
+///
+/// #[derive(Debug, Snafu)]
+/// #[snafu(display("Expected {right} items but found {left}"))]
+/// struct CountMismatchError {
+///     left: usize,
+///     right: usize,
+/// }
+///
+/// fn example(items: &[u8], expected: usize) -> Result<(), CountMismatchError> {
+///     ensure_eq!(items.len(), expected, |left, right| CountMismatchSnafu {
+///         left: *left,
+///         right: *right,
+///     });
+///     Ok(())
+/// }
+///
+/// let err = example(&[1, 2, 3], 2).unwrap_err();
+/// assert_eq!(err.to_string(), "Expected 2 items but found 3");
+/// ```
+#[macro_export]
+macro_rules! ensure_eq {
+    ($left:expr, $right:expr, |$l:pat_param, $r:pat_param| $context_selector:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left_val, right_val) => {
+                if !(*left_val == *right_val) {
+                    let $l = left_val;
+                    let $r = right_val;
+                    return $context_selector
+                        .fail()
+                        .map_err(::core::convert::Into::into);
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $context_selector:expr $(,)?) => {
+        $crate::ensure_eq!($left, $right, |_, _| $context_selector);
+    };
+}
+
+/// Ensure two values are not equal. If they are, return from the
+/// function with an error.
+///
+/// This behaves the same as [`ensure_eq!`][crate::ensure_eq!],
+/// including the closure form that provides access to the compared
+/// values.
+///
+/// ## Examples
+///
+/// ```rust
+/// use snafu::prelude::*;
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     SelfTransfer { account_id: u32 },
+/// }
+///
+/// fn transfer(from: u32, to: u32) -> Result<(), Error> {
+///     ensure_ne!(from, to, |account_id, _| SelfTransferSnafu {
+///         account_id: *account_id,
+///     });
+///     Ok(())
+/// }
+/// ```
+#[macro_export]
+macro_rules! ensure_ne {
+    ($left:expr, $right:expr, |$l:pat_param, $r:pat_param| $context_selector:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left_val, right_val) => {
+                if *left_val == *right_val {
+                    let $l = left_val;
+                    let $r = right_val;
+                    return $context_selector
+                        .fail()
+                        .map_err(::core::convert::Into::into);
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $context_selector:expr $(,)?) => {
+        $crate::ensure_ne!($left, $right, |_, _| $context_selector);
+    };
+}
+
+/// Ensure an [`Option`][] contains a value. If it does not, return
+/// from the function with an error. Otherwise, the macro evaluates to
+/// the contained value.
+///
+/// This is the early-return counterpart to
+/// [`OptionExt::context`][crate::OptionExt::context] and accepts the
+/// same context selectors.
+///
+/// ## Examples
+///
+/// ```rust
+/// use snafu::prelude::*;
+/// use std::collections::HashMap;
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     UserLookup { user_id: i32 },
+/// }
+///
+/// fn example(users: &HashMap<i32, String>, user_id: i32) -> Result<usize, Error> {
+///     let name = ensure_some!(users.get(&user_id), UserLookupSnafu { user_id });
+///     Ok(name.len())
+/// }
+/// ```
+#[macro_export]
+macro_rules! ensure_some {
+    ($option:expr, $context_selector:expr $(,)?) => {
+        match $option {
+            ::core::option::Option::Some(v) => v,
+            ::core::option::Option::None => {
+                return ::core::result::Result::Err(::core::convert::Into::into(
+                    $crate::IntoError::into_error($context_selector, $crate::NoneError),
+                ));
+            }
+        }
+    };
+}
+
+/// Ensure a [`Result`][] is successful. If it is not, return from the
+/// function with an error that uses the original error as its
+/// source. Otherwise, the macro evaluates to the `Ok` value.
+///
+/// This is the early-return counterpart to
+/// [`ResultExt::context`][crate::ResultExt::context] and accepts the
+/// same context selectors.
+///
+/// ## Examples
+///
+/// ```rust
+/// use snafu::prelude::*;
+///
+/// #[derive(Debug, Snafu)]
+/// enum Error {
+///     InvalidPort {
+///         source: std::num::ParseIntError,
+///         text: String,
+///     },
+/// }
+///
+/// fn example(text: &str) -> Result<u16, Error> {
+///     let port = ensure_ok!(text.parse(), InvalidPortSnafu { text });
+///     Ok(port)
+/// }
+/// ```
+#[macro_export]
+macro_rules! ensure_ok {
+    ($result:expr, $context_selector:expr $(,)?) => {
+        match $result {
+            ::core::result::Result::Ok(v) => v,
+            ::core::result::Result::Err(e) => {
+                return ::core::result::Result::Err(::core::convert::Into::into(
+                    $crate::IntoError::into_error($context_selector, e),
+                ));
+            }
+        }
+    };
+}
+
 #[cfg(feature = "alloc")]
 #[doc(hidden)]
 pub use alloc::format as __format;
@@ -596,6 +858,141 @@ macro_rules! ensure_whatever {
     };
 }
 
+/// Ensure two values are equal. If they are not, return a
+/// stringly-typed error message.
+///
+/// This is built on [`ensure_whatever!`][crate::ensure_whatever!]. As
+/// with [`ensure_eq!`][crate::ensure_eq!], the message may be preceded
+/// by a closure-like pair of parameters that are bound to references
+/// to the left and right values. These bindings are only created when
+/// the values are not equal.
+///
+/// ## Examples
+///
+/// ```rust
+/// use snafu::{prelude::*, Whatever};
+///
+/// fn example(checksum: u32, expected: u32) -> Result<(), Whatever> {
+///     ensure_whatever_eq!(checksum, expected, |actual, expected| {
+///         "Checksum {actual:#x} did not match {expected:#x}"
+///     });
+///     Ok(())
+/// }
+///
+/// let err = example(0xdead, 0xbeef).unwrap_err();
+/// assert_eq!(err.to_string(), "Checksum 0xdead did not match 0xbeef");
+/// ```
+#[macro_export]
+#[cfg(any(feature = "alloc", test))]
+macro_rules! ensure_whatever_eq {
+    ($left:expr, $right:expr, |$l:pat_param, $r:pat_param| $fmt:literal$(, $($arg:expr),* $(,)?)?) => {
+        $crate::ensure_whatever_eq!($left, $right, |$l, $r| { $fmt$(, $($arg),*)* });
+    };
+    ($left:expr, $right:expr, |$l:pat_param, $r:pat_param| { $fmt:literal$(, $($arg:expr),* $(,)?)? } $(,)?) => {
+        match (&$left, &$right) {
+            (left_val, right_val) => {
+                if !(*left_val == *right_val) {
+                    let $l = left_val;
+                    let $r = right_val;
+                    $crate::whatever!($fmt$(, $($arg),*)*);
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $fmt:literal$(, $($arg:expr),* $(,)?)?) => {
+        $crate::ensure_whatever!($left == $right, $fmt$(, $($arg),*)*);
+    };
+}
+
+/// Ensure two values are not equal. If they are, return a
+/// stringly-typed error message.
+///
+/// This behaves the same as
+/// [`ensure_whatever_eq!`][crate::ensure_whatever_eq!], including the
+/// closure-like form that provides access to the compared values.
+///
+/// ## Examples
+///
+/// ```rust
+/// use snafu::{prelude::*, Whatever};
+///
+/// fn example(new_password: &str, old_password: &str) -> Result<(), Whatever> {
+///     ensure_whatever_ne!(new_password, old_password, "The password must change");
+///     Ok(())
+/// }
+/// ```
+#[macro_export]
+#[cfg(any(feature = "alloc", test))]
+macro_rules! ensure_whatever_ne {
+    ($left:expr, $right:expr, |$l:pat_param, $r:pat_param| $fmt:literal$(, $($arg:expr),* $(,)?)?) => {
+        $crate::ensure_whatever_ne!($left, $right, |$l, $r| { $fmt$(, $($arg),*)* });
+    };
+    ($left:expr, $right:expr, |$l:pat_param, $r:pat_param| { $fmt:literal$(, $($arg:expr),* $(,)?)? } $(,)?) => {
+        match (&$left, &$right) {
+            (left_val, right_val) => {
+                if *left_val == *right_val {
+                    let $l = left_val;
+                    let $r = right_val;
+                    $crate::whatever!($fmt$(, $($arg),*)*);
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $fmt:literal$(, $($arg:expr),* $(,)?)?) => {
+        $crate::ensure_whatever!($left != $right, $fmt$(, $($arg),*)*);
+    };
+}
+
+/// Ensure an [`Option`][] contains a value. If it does not, return a
+/// stringly-typed error message. Otherwise, the macro evaluates to the
+/// contained value.
+///
+/// ## Examples
+///
+/// ```rust
+/// use snafu::{prelude::*, Whatever};
+///
+/// fn example(args: &[&str]) -> Result<usize, Whatever> {
+///     let first = ensure_whatever_some!(args.first(), "At least one argument is required");
+///     Ok(first.len())
+/// }
+/// ```
+#[macro_export]
+#[cfg(any(feature = "alloc", test))]
+macro_rules! ensure_whatever_some {
+    ($option:expr, $fmt:literal$(, $($arg:expr),* $(,)?)?) => {
+        match $option {
+            ::core::option::Option::Some(v) => v,
+            ::core::option::Option::None => $crate::whatever!($fmt$(, $($arg),*)*),
+        }
+    };
+}
+
+/// Ensure a [`Result`][] is successful. If it is not, return a
+/// stringly-typed error message that uses the original error as its
+/// source. Otherwise, the macro evaluates to the `Ok` value.
+///
+/// This is equivalent to the [`whatever!`][crate::whatever!] form that
+/// accepts an underlying error.
+///
+/// ## Examples
+///
+/// ```rust
+/// use snafu::{prelude::*, Whatever};
+///
+/// fn example(text: &str) -> Result<u16, Whatever> {
+///     let port = ensure_whatever_ok!(text.parse(), "Invalid port {text}");
+///     Ok(port)
+/// }
+/// ```
+#[macro_export]
+#[cfg(any(feature = "alloc", test))]
+macro_rules! ensure_whatever_ok {
+    ($result:expr, $fmt:literal$(, $($arg:expr),* $(,)?)?) => {
+        $crate::whatever!($result, $fmt$(, $($arg),*)*)
+    };
+}
+
 /// Additions to [`Result`][].
 pub trait ResultExt<T, E>: Sized {
     /// Extend a [`Result`]'s error with additional context-sensitive information.
@@ -957,6 +1354,33 @@ impl<T, E> ResultExt<T, E> for Result<T, E> {
 /// [`Result`]: std::result::Result
 pub struct NoneError;
 
+/// A description of the value that was missing when an error was
+/// created by [`OptionExt::expect_context`][].
+///
+/// Use it as [implicitly generated data][implicit] to record the
+/// description in the error. When the error is created any other way,
+/// the description is empty.
+///
+/// [implicit]: Snafu#controlling-implicit-data
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Expected(pub &'static str);
+
+impl core::fmt::Display for Expected {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl GenerateImplicitData for Expected {
+    fn generate() -> Self {
+        Self::default()
+    }
+
+    fn generate_with_expected(expected: Expected) -> Self {
+        expected
+    }
+}
+
 /// Additions to [`Option`][].
 pub trait OptionExt<T>: Sized {
     /// Convert an [`Option`][] into a [`Result`][] with additional
@@ -1032,6 +1456,43 @@ pub trait OptionExt<T>: Sized {
         C: IntoError<E, Source = NoneError>,
         E: Error + ErrorCompat;
 
+    /// Convert an [`Option`][] into a [`Result`][] with additional
+    /// context-sensitive information and a description of what was
+    /// expected.
+    ///
+    /// The description is used for implicit fields of type
+    /// [`Expected`][]:
+    ///
+    /// [`Option`]: std::option::Option
+    /// [`Result`]: std::result::Result
+    ///
+    /// ```rust
+    /// use snafu::{prelude::*, Expected};
+    /// use std::collections::HashMap;
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// #[snafu(display("Missing {what} for user {user_id}"))]
+    /// struct MissingError {
+    ///     user_id: i32,
+    ///     #[snafu(implicit)]
+    ///     what: Expected,
+    /// }
+    ///
+    /// fn email(users: &HashMap<i32, String>, user_id: i32) -> Result<&str, MissingError> {
+    ///     let email = users
+    ///         .get(&user_id)
+    ///         .expect_context("an email address", MissingSnafu { user_id })?;
+    ///     Ok(email)
+    /// }
+    ///
+    /// let e = email(&HashMap::new(), 42).unwrap_err();
+    /// assert_eq!(e.to_string(), "Missing an email address for user 42");
+    /// ```
+    fn expect_context<C, E>(self, what: &'static str, context: C) -> Result<T, E>
+    where
+        C: IntoError<E, Source = NoneError>,
+        E: Error + ErrorCompat;
+
     /// Convert an [`Option`] into a [`Result`] with information
     /// from a string.
     ///
@@ -1135,6 +1596,19 @@ impl<T> OptionExt<T> for Option<T> {
         }
     }
 
+    #[track_caller]
+    fn expect_context<C, E>(self, what: &'static str, context: C) -> Result<T, E>
+    where
+        C: IntoError<E, Source = NoneError>,
+        E: Error + ErrorCompat,
+    {
+        // https://github.com/rust-lang/rust/issues/74042
+        match self {
+            Some(v) => Ok(v),
+            None => Err(context.into_error_expecting(NoneError, Expected(what))),
+        }
+    }
+
     #[cfg(any(feature = "alloc", test))]
     #[track_caller]
     fn whatever_context<S, E>(self, context: S) -> Result<T, E>
@@ -1166,41 +1640,302 @@ impl<T> OptionExt<T> for Option<T> {
     }
 }
 
-/// Backports changes to the [`Error`][] trait to versions of Rust
-/// lacking them.
-///
-/// It is recommended to always call these methods explicitly so that
-/// it is easy to replace usages of this trait when you start
-/// supporting a newer version of Rust.
-///
-/// ```
-/// # use snafu::{prelude::*, ErrorCompat};
-/// # #[derive(Debug, Snafu)] enum Example {};
-/// # fn example(error: Example) {
-/// ErrorCompat::backtrace(&error); // Recommended
-/// error.backtrace();              // Discouraged
-/// # }
-/// ```
-pub trait ErrorCompat {
-    /// Returns a [`Backtrace`][] that may be printed.
-    fn backtrace(&self) -> Option<&Backtrace> {
-        None
-    }
-
-    /// Returns an iterator for traversing the chain of errors,
-    /// starting with the current error
-    /// and continuing with recursive calls to `Error::source`.
+/// Additions to [`bool`][].
+pub trait BoolExt: Sized {
+    /// Convert a condition into a [`Result`][], failing with
+    /// additional context-sensitive information when it is `false`.
     ///
-    /// To omit the current error and only traverse its sources,
-    /// use `skip(1)`.
-    fn iter_chain(&self) -> ChainCompat<'_, '_>
-    where
-        Self: AsErrorSource,
-    {
-        ChainCompat::new(self.as_error_source())
-    }
-}
-
+    /// This is the method form of [`ensure!`][].
+    ///
+    /// [`Result`]: std::result::Result
+    ///
+    /// ```rust
+    /// use snafu::prelude::*;
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     InvalidUser { user_id: i32 },
+    /// }
+    ///
+    /// fn example(user_id: i32) -> Result<(), Error> {
+    ///     (user_id > 0).context(InvalidUserSnafu { user_id })?;
+    ///     Ok(())
+    /// }
+    ///
+    /// assert!(example(-1).is_err());
+    /// ```
+    fn context<C, E>(self, context: C) -> Result<(), E>
+    where
+        C: IntoError<E, Source = NoneError>,
+        E: Error + ErrorCompat;
+
+    /// Convert a condition into a [`Result`][], failing with
+    /// lazily-generated context-sensitive information when it is
+    /// `false`.
+    ///
+    /// [`Result`]: std::result::Result
+    ///
+    /// ```rust
+    /// use snafu::prelude::*;
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     TooLong { name: String },
+    /// }
+    ///
+    /// fn example(name: &str) -> Result<(), Error> {
+    ///     (name.len() < 8).with_context(|| TooLongSnafu { name })?;
+    ///     Ok(())
+    /// }
+    ///
+    /// assert!(example("a very long name").is_err());
+    /// ```
+    fn with_context<F, C, E>(self, context: F) -> Result<(), E>
+    where
+        F: FnOnce() -> C,
+        C: IntoError<E, Source = NoneError>,
+        E: Error + ErrorCompat;
+
+    /// Convert a condition into a [`Result`][], failing with
+    /// additional context-sensitive information when it is `true`.
+    ///
+    /// This reads naturally in guard clauses that check for a
+    /// problem.
+    ///
+    /// [`Result`]: std::result::Result
+    ///
+    /// ```rust
+    /// use snafu::prelude::*;
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// enum Error {
+    ///     NoUsers,
+    /// }
+    ///
+    /// fn example(users: &[&str]) -> Result<(), Error> {
+    ///     users.is_empty().then_fail(NoUsersSnafu)?;
+    ///     Ok(())
+    /// }
+    ///
+    /// assert!(example(&[]).is_err());
+    /// ```
+    fn then_fail<C, E>(self, context: C) -> Result<(), E>
+    where
+        C: IntoError<E, Source = NoneError>,
+        E: Error + ErrorCompat;
+
+    /// Convert a condition into a [`Result`][], failing with
+    /// information from a string when it is `false`.
+    ///
+    /// The target error type must implement [`FromString`][] by using
+    /// the
+    /// [`#[snafu(whatever)]`][Snafu#controlling-stringly-typed-errors]
+    /// attribute. The premade [`Whatever`][] type is also available.
+    ///
+    /// [`Result`]: std::result::Result
+    ///
+    /// ```rust
+    /// use snafu::{prelude::*, Whatever};
+    ///
+    /// fn example(retries: u8) -> Result<(), Whatever> {
+    ///     (retries < 3).whatever_context("too many retries")?;
+    ///     Ok(())
+    /// }
+    ///
+    /// let err = example(5).unwrap_err();
+    /// assert_eq!("too many retries", err.to_string());
+    /// ```
+    #[cfg(any(feature = "alloc", test))]
+    fn whatever_context<S, E>(self, context: S) -> Result<(), E>
+    where
+        S: Into<String>,
+        E: FromString;
+
+    /// Convert a condition into a [`Result`][], failing with
+    /// information from a lazily-generated string when it is
+    /// `false`.
+    ///
+    /// The target error type must implement [`FromString`][] by using
+    /// the
+    /// [`#[snafu(whatever)]`][Snafu#controlling-stringly-typed-errors]
+    /// attribute. The premade [`Whatever`][] type is also available.
+    ///
+    /// [`Result`]: std::result::Result
+    ///
+    /// ```rust
+    /// use snafu::{prelude::*, Whatever};
+    ///
+    /// fn example(retries: u8) -> Result<(), Whatever> {
+    ///     (retries < 3).with_whatever_context(|| format!("{retries} retries is too many"))?;
+    ///     Ok(())
+    /// }
+    ///
+    /// let err = example(5).unwrap_err();
+    /// assert_eq!("5 retries is too many", err.to_string());
+    /// ```
+    #[cfg(any(feature = "alloc", test))]
+    fn with_whatever_context<F, S, E>(self, context: F) -> Result<(), E>
+    where
+        F: FnOnce() -> S,
+        S: Into<String>,
+        E: FromString;
+}
+
+impl BoolExt for bool {
+    #[track_caller]
+    fn context<C, E>(self, context: C) -> Result<(), E>
+    where
+        C: IntoError<E, Source = NoneError>,
+        E: Error + ErrorCompat,
+    {
+        if self {
+            Ok(())
+        } else {
+            Err(context.into_error(NoneError))
+        }
+    }
+
+    #[track_caller]
+    fn with_context<F, C, E>(self, context: F) -> Result<(), E>
+    where
+        F: FnOnce() -> C,
+        C: IntoError<E, Source = NoneError>,
+        E: Error + ErrorCompat,
+    {
+        if self {
+            Ok(())
+        } else {
+            Err(context().into_error(NoneError))
+        }
+    }
+
+    #[track_caller]
+    fn then_fail<C, E>(self, context: C) -> Result<(), E>
+    where
+        C: IntoError<E, Source = NoneError>,
+        E: Error + ErrorCompat,
+    {
+        (!self).context(context)
+    }
+
+    #[cfg(any(feature = "alloc", test))]
+    #[track_caller]
+    fn whatever_context<S, E>(self, context: S) -> Result<(), E>
+    where
+        S: Into<String>,
+        E: FromString,
+    {
+        if self {
+            Ok(())
+        } else {
+            Err(FromString::without_source(context.into()))
+        }
+    }
+
+    #[cfg(any(feature = "alloc", test))]
+    #[track_caller]
+    fn with_whatever_context<F, S, E>(self, context: F) -> Result<(), E>
+    where
+        F: FnOnce() -> S,
+        S: Into<String>,
+        E: FromString,
+    {
+        if self {
+            Ok(())
+        } else {
+            let context = context();
+            Err(FromString::without_source(context.into()))
+        }
+    }
+}
+
+/// Backports changes to the [`Error`][] trait to versions of Rust
+/// lacking them.
+///
+/// It is recommended to always call these methods explicitly so that
+/// it is easy to replace usages of this trait when you start
+/// supporting a newer version of Rust.
+///
+/// ```
+/// # use snafu::{prelude::*, ErrorCompat};
+/// # #[derive(Debug, Snafu)] enum Example {};
+/// # fn example(error: Example) {
+/// ErrorCompat::backtrace(&error); // Recommended
+/// error.backtrace();              // Discouraged
+/// # }
+/// ```
+pub trait ErrorCompat {
+    /// Returns a [`Backtrace`][] that may be printed.
+    fn backtrace(&self) -> Option<&Backtrace> {
+        None
+    }
+
+    /// Returns the [`Location`][] where this error was created, taken
+    /// from the first implicit field of type `Location`. Fields whose
+    /// type is a generic parameter are not considered.
+    fn location(&self) -> Option<Location> {
+        None
+    }
+
+    /// Returns the [`LocationTrace`][] of this error, taken from the
+    /// first implicit field of that type.
+    fn location_trace(&self) -> Option<&LocationTrace> {
+        None
+    }
+
+    /// Returns whether the [`Display`][core::fmt::Display] output of
+    /// this error contains the message of its source, when that is
+    /// known from `#[snafu(display_source)]`.
+    fn display_includes_source(&self) -> Option<bool> {
+        None
+    }
+
+    /// Returns the [`Backtrace`][] from the deepest error in the
+    /// chain that has one. Errors that derive [`Snafu`][] look for a
+    /// backtrace in their source when it also implements
+    /// `ErrorCompat`, which works on stable Rust.
+    ///
+    /// ```rust
+    /// use snafu::{prelude::*, Backtrace, ErrorCompat};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// struct InnerError {
+    ///     backtrace: Backtrace,
+    /// }
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// struct OuterError {
+    ///     source: InnerError,
+    ///     backtrace: Backtrace,
+    /// }
+    ///
+    /// fn inner() -> Result<(), InnerError> {
+    ///     InnerSnafu.fail()
+    /// }
+    ///
+    /// let e = inner().context(OuterSnafu).unwrap_err();
+    /// let deepest = e.deepest_backtrace().map(|b| b as *const Backtrace);
+    /// let inner = ErrorCompat::backtrace(&e.source).map(|b| b as *const Backtrace);
+    /// assert_eq!(deepest, inner);
+    /// ```
+    fn deepest_backtrace(&self) -> Option<&Backtrace> {
+        self.backtrace()
+    }
+
+    /// Returns an iterator for traversing the chain of errors,
+    /// starting with the current error
+    /// and continuing with recursive calls to `Error::source`.
+    ///
+    /// To omit the current error and only traverse its sources,
+    /// use `skip(1)`.
+    fn iter_chain(&self) -> ChainCompat<'_, '_>
+    where
+        Self: AsErrorSource,
+    {
+        ChainCompat::new(self.as_error_source())
+    }
+}
+
 impl<'a, E> ErrorCompat for &'a E
 where
     E: ErrorCompat,
@@ -1208,6 +1943,22 @@ where
     fn backtrace(&self) -> Option<&Backtrace> {
         (**self).backtrace()
     }
+
+    fn location(&self) -> Option<Location> {
+        (**self).location()
+    }
+
+    fn location_trace(&self) -> Option<&LocationTrace> {
+        (**self).location_trace()
+    }
+
+    fn display_includes_source(&self) -> Option<bool> {
+        (**self).display_includes_source()
+    }
+
+    fn deepest_backtrace(&self) -> Option<&Backtrace> {
+        (**self).deepest_backtrace()
+    }
 }
 
 /// Converts the receiver into an [`Error`][] trait object, suitable
@@ -1305,7 +2056,25 @@ where
     type Source;
 
     /// Combine the information to produce the error
+    ///
+    /// Implementations capture the location of their caller, so
+    /// [`Location`][] fields record where the error was created.
+    #[track_caller]
     fn into_error(self, source: Self::Source) -> E;
+
+    /// Combine the information to produce the error, recording what
+    /// was [`Expected`][]. This is used by
+    /// [`OptionExt::expect_context`][].
+    ///
+    /// By default, the description is ignored.
+    #[track_caller]
+    fn into_error_expecting(self, source: Self::Source, expected: Expected) -> E
+    where
+        Self: Sized,
+    {
+        let _expected = expected;
+        self.into_error(source)
+    }
 }
 
 /// Takes a string message and builds the corresponding error.
@@ -1318,27 +2087,58 @@ pub trait FromString {
     type Source;
 
     /// Create a brand new error from the given string
+    #[track_caller]
     fn without_source(message: String) -> Self;
 
     /// Wrap an existing error with the given string
+    #[track_caller]
     fn with_source(source: Self::Source, message: String) -> Self;
 }
 
 /// Construct data to be included as part of an error. The data must
 /// require no arguments to be created.
+///
+/// Every method is `#[track_caller]`, including in implementations
+/// that override the provided methods, so `core::panic::Location::caller`
+/// is where the error was created.
 pub trait GenerateImplicitData {
     /// Build the data.
+    #[track_caller]
     fn generate() -> Self;
 
     /// Build the data using the given source
     #[track_caller]
-    fn generate_with_source(source: &dyn crate::Error) -> Self
+    fn generate_with_source(source: &(dyn crate::Error + 'static)) -> Self
     where
         Self: Sized,
     {
         let _source = source;
         Self::generate()
     }
+
+    #[doc(hidden)]
+    #[track_caller]
+    fn __snafu_generate_with_compat(
+        source: &(dyn crate::Error + 'static),
+        compat: __SourceCompat<'_>,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        let _compat = compat;
+        Self::generate_with_source(source)
+    }
+
+    /// Build the data for an error created by
+    /// [`OptionExt::expect_context`][].
+    #[track_caller]
+    fn generate_with_expected(expected: Expected) -> Self
+    where
+        Self: Sized,
+    {
+        let _expected = expected;
+        Self::generate()
+    }
 }
 
 /// View a backtrace-like value as an optional backtrace.
@@ -1347,15 +2147,12 @@ pub trait AsBacktrace {
     fn as_backtrace(&self) -> Option<&Backtrace>;
 }
 
-/// Only create a backtrace when an environment variable is set.
-///
-/// This looks first for the value of `RUST_LIB_BACKTRACE` then
-/// `RUST_BACKTRACE`. If the value is set to `1`, backtraces will be
-/// enabled.
+/// Only create a backtrace when the capture policy allows it.
 ///
-/// This value will be tested only once per program execution;
-/// changing the environment variable after it has been checked will
-/// have no effect.
+/// By default, this looks first for the value of `RUST_LIB_BACKTRACE`
+/// then `RUST_BACKTRACE`. If the value is set to anything other than
+/// `0`, backtraces will be enabled. See [`backtrace::set_capture`][]
+/// to change this at runtime.
 ///
 /// ## Interaction with the Provider API
 ///
@@ -1368,6 +2165,7 @@ pub trait AsBacktrace {
 /// [provider-ff]: crate::guide::feature_flags#unstable-provider-api
 #[cfg(any(feature = "std", test))]
 impl GenerateImplicitData for Option<Backtrace> {
+    #[track_caller]
     fn generate() -> Self {
         if backtrace_collection_enabled() {
             Some(Backtrace::generate())
@@ -1376,12 +2174,13 @@ impl GenerateImplicitData for Option<Backtrace> {
         }
     }
 
-    fn generate_with_source(source: &dyn crate::Error) -> Self {
+    #[track_caller]
+    fn generate_with_source(source: &(dyn crate::Error + 'static)) -> Self {
         #[cfg(feature = "unstable-provider-api")]
         {
-            if !backtrace_collection_enabled() {
+            if backtraces(source).next().is_some() {
                 None
-            } else if backtraces(source).next().is_some() {
+            } else if !backtrace_collection_enabled() {
                 None
             } else {
                 Some(Backtrace::generate_with_source(source))
@@ -1403,19 +2202,55 @@ impl AsBacktrace for Option<Backtrace> {
     }
 }
 
+/// Only create a backtrace when the capture policy allows it,
+/// recording the location when [`Policy::RateLimited`][] skips it.
+///
+/// This interacts with the Provider API in the same way as
+/// `Option<Backtrace>`.
+///
+/// [`Policy::RateLimited`]: crate::backtrace::Policy::RateLimited
 #[cfg(any(feature = "std", test))]
-fn backtrace_collection_enabled() -> bool {
-    use crate::once_bool::OnceBool;
-    use std::env;
+impl GenerateImplicitData for backtrace::OptionalBacktrace {
+    #[track_caller]
+    fn generate() -> Self {
+        use crate::backtrace::Skipped;
 
-    static ENABLED: OnceBool = OnceBool::new();
+        let location = core::panic::Location::caller();
+        match crate::backtrace::check_capture(location) {
+            Ok(()) => Self::Captured(Backtrace::generate()),
+            Err(Skipped::ByPolicy) => Self::NotCaptured,
+            Err(Skipped::RateLimited) => Self::RateLimited(location),
+        }
+    }
 
-    ENABLED.get(|| {
-        // TODO: What values count as "true"?
-        env::var_os("RUST_LIB_BACKTRACE")
-            .or_else(|| env::var_os("RUST_BACKTRACE"))
-            .map_or(false, |v| v == "1")
-    })
+    #[track_caller]
+    fn generate_with_source(source: &(dyn crate::Error + 'static)) -> Self {
+        #[cfg(feature = "unstable-provider-api")]
+        {
+            if backtraces(source).next().is_some() {
+                return Self::NotCaptured;
+            }
+        }
+
+        let _source = source;
+        Self::generate()
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl AsBacktrace for backtrace::OptionalBacktrace {
+    fn as_backtrace(&self) -> Option<&Backtrace> {
+        match self {
+            Self::Captured(backtrace) => Some(backtrace),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+#[track_caller]
+fn backtrace_collection_enabled() -> bool {
+    crate::backtrace::check_capture(core::panic::Location::caller()).is_ok()
 }
 
 /// The source code location where the error was reported.
@@ -1573,6 +2408,133 @@ impl GenerateImplicitData for Location {
     }
 }
 
+// Implicit fields are checked for a `Location` using method
+// resolution: the implementation for `Location` is on
+// `&LocationRef` and the fallback for every other type is on
+// `LocationRef`, so calling `(&&LocationRef(v)).__snafu_location()`
+// picks the first one that applies.
+
+#[doc(hidden)]
+pub struct __LocationRef<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait __IsLocation {
+    fn __snafu_location(&self) -> Option<Location>;
+}
+
+impl __IsLocation for &__LocationRef<'_, Location> {
+    fn __snafu_location(&self) -> Option<Location> {
+        Some(*self.0)
+    }
+}
+
+impl __IsLocation for &__LocationRef<'_, LocationTrace> {
+    fn __snafu_location(&self) -> Option<Location> {
+        Some(self.0.last())
+    }
+}
+
+#[doc(hidden)]
+pub trait __NotLocation {
+    fn __snafu_location(&self) -> Option<Location>;
+}
+
+impl<T> __NotLocation for __LocationRef<'_, T> {
+    fn __snafu_location(&self) -> Option<Location> {
+        None
+    }
+}
+
+// Implicit fields are checked for a `LocationTrace` in the same way.
+
+#[doc(hidden)]
+pub struct __LocationTraceRef<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait __IsLocationTrace<'a> {
+    fn __snafu_location_trace(&self) -> Option<&'a LocationTrace>;
+}
+
+impl<'a> __IsLocationTrace<'a> for &__LocationTraceRef<'a, LocationTrace> {
+    fn __snafu_location_trace(&self) -> Option<&'a LocationTrace> {
+        Some(self.0)
+    }
+}
+
+#[doc(hidden)]
+pub trait __NotLocationTrace<'a> {
+    fn __snafu_location_trace(&self) -> Option<&'a LocationTrace>;
+}
+
+impl<'a, T> __NotLocationTrace<'a> for __LocationTraceRef<'a, T> {
+    fn __snafu_location_trace(&self) -> Option<&'a LocationTrace> {
+        None
+    }
+}
+
+// The source field is checked for an `ErrorCompat` implementation in
+// the same way as implicit fields are checked for a `Location`.
+
+#[doc(hidden)]
+pub struct __CompatRef<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait __IsErrorCompat<'a> {
+    fn __snafu_deepest_backtrace(&self) -> Option<&'a Backtrace>;
+    fn __snafu_compat_location_trace(&self) -> Option<&'a LocationTrace>;
+    fn __snafu_source_compat(&self) -> __SourceCompat<'a>;
+}
+
+impl<'a, T> __IsErrorCompat<'a> for &__CompatRef<'a, T>
+where
+    T: ErrorCompat,
+{
+    fn __snafu_deepest_backtrace(&self) -> Option<&'a Backtrace> {
+        self.0.deepest_backtrace()
+    }
+
+    fn __snafu_compat_location_trace(&self) -> Option<&'a LocationTrace> {
+        self.0.location_trace()
+    }
+
+    fn __snafu_source_compat(&self) -> __SourceCompat<'a> {
+        __SourceCompat {
+            location: self.0.location(),
+            location_trace: self.0.location_trace(),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub trait __NotErrorCompat<'a> {
+    fn __snafu_deepest_backtrace(&self) -> Option<&'a Backtrace>;
+    fn __snafu_compat_location_trace(&self) -> Option<&'a LocationTrace>;
+    fn __snafu_source_compat(&self) -> __SourceCompat<'a>;
+}
+
+impl<'a, T> __NotErrorCompat<'a> for __CompatRef<'a, T> {
+    fn __snafu_deepest_backtrace(&self) -> Option<&'a Backtrace> {
+        None
+    }
+
+    fn __snafu_compat_location_trace(&self) -> Option<&'a LocationTrace> {
+        None
+    }
+
+    fn __snafu_source_compat(&self) -> __SourceCompat<'a> {
+        __SourceCompat::default()
+    }
+}
+
+/// What the [`ErrorCompat`][] implementation of a source knows about
+/// it, passed to implicit data created from that source.
+#[doc(hidden)]
+#[derive(Debug, Copy, Clone, Default)]
+pub struct __SourceCompat<'a> {
+    pub location: Option<Location>,
+    pub location_trace: Option<&'a LocationTrace>,
+}
+
 /// Constructs a [`Location`] using the current file, line, and column.
 #[macro_export]
 macro_rules! location {
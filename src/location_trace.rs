@@ -0,0 +1,180 @@
+use crate::{GenerateImplicitData, Location};
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use crate::{__SourceCompat, ChainCompat};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// The source code locations an error travelled through as it was
+/// wrapped by other errors, from where it was first created to where
+/// it was last wrapped.
+///
+/// To use it, add a field of type `LocationTrace` to your errors and
+/// register it as [implicitly generated data][implicit]. When an
+/// error is created from a source, the trace of the source is copied
+/// and the current location is appended to it:
+///
+/// ```rust
+/// use snafu::{prelude::*, IntoError, LocationTrace};
+///
+/// #[derive(Debug, Snafu)]
+/// struct ConnectError {
+///     #[snafu(implicit)]
+///     trace: LocationTrace,
+/// }
+///
+/// #[derive(Debug, Snafu)]
+/// struct StartupError {
+///     source: ConnectError,
+///     #[snafu(implicit)]
+///     trace: LocationTrace,
+/// }
+///
+/// let e = ConnectSnafu.build();
+/// let e = StartupSnafu.into_error(e);
+///
+/// let StartupError { trace, .. } = e;
+/// assert_eq!(trace.locations().len(), 2);
+/// ```
+///
+/// The trace of the source is found with its
+/// [`ErrorCompat`][crate::ErrorCompat] implementation or, further
+/// down its chain of errors, when it is [provided][provider-ff]. When
+/// no source has a trace, the known [`Location`][]s of the sources
+/// are used instead. A [`Report`][crate::Report] displays the trace of the
+/// error, including the locations of `transparent` variants, which do
+/// not appear in the chain of errors themselves.
+///
+/// Without the `alloc` feature flag, only the most recent location
+/// is kept.
+///
+/// [implicit]: crate::Snafu#controlling-implicit-data
+/// [provider-ff]: crate::guide::feature_flags#unstable-provider-api
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocationTrace {
+    #[cfg(feature = "alloc")]
+    locations: Vec<Location>,
+
+    #[cfg(not(feature = "alloc"))]
+    locations: [Location; 1],
+}
+
+impl LocationTrace {
+    /// The locations, starting with the oldest.
+    pub fn locations(&self) -> &[Location] {
+        &self.locations
+    }
+
+    /// The location where the error was first created.
+    pub fn first(&self) -> Location {
+        self.locations[0]
+    }
+
+    /// The location where the error was most recently wrapped.
+    pub fn last(&self) -> Location {
+        self.locations[self.locations.len() - 1]
+    }
+}
+
+impl GenerateImplicitData for LocationTrace {
+    #[track_caller]
+    fn generate() -> Self {
+        let caller = core::panic::Location::caller();
+
+        #[cfg(feature = "alloc")]
+        {
+            Self {
+                locations: alloc::vec![caller],
+            }
+        }
+
+        #[cfg(not(feature = "alloc"))]
+        {
+            Self {
+                locations: [caller],
+            }
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[track_caller]
+    fn generate_with_source(source: &(dyn crate::Error + 'static)) -> Self {
+        Self::__snafu_generate_with_compat(source, __SourceCompat::default())
+    }
+
+    #[cfg(feature = "alloc")]
+    #[track_caller]
+    fn __snafu_generate_with_compat(
+        source: &(dyn crate::Error + 'static),
+        compat: __SourceCompat<'_>,
+    ) -> Self {
+        let trace = compat
+            .location_trace
+            .or_else(|| ChainCompat::new(source).find_map(provided_location_trace));
+
+        let mut locations = match trace {
+            Some(trace) => trace.locations.clone(),
+            None => {
+                let mut locations: Vec<_> = compat
+                    .location
+                    .or_else(|| provided_location(source))
+                    .into_iter()
+                    .chain(
+                        ChainCompat::new(source)
+                            .skip(1)
+                            .filter_map(provided_location),
+                    )
+                    .collect();
+                locations.reverse();
+                locations
+            }
+        };
+
+        locations.push(core::panic::Location::caller());
+
+        Self { locations }
+    }
+}
+
+impl fmt::Display for LocationTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, location) in self.locations.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" -> ")?;
+            }
+            write!(f, "{}", location)?;
+        }
+        Ok(())
+    }
+}
+
+/// The location of the error, if it is provided.
+#[cfg(feature = "alloc")]
+fn provided_location(e: &(dyn crate::Error + 'static)) -> Option<Location> {
+    #[cfg(feature = "unstable-provider-api")]
+    {
+        crate::error::request_ref::<Location>(e).copied()
+    }
+
+    #[cfg(not(feature = "unstable-provider-api"))]
+    {
+        let _e = e;
+        None
+    }
+}
+
+/// The trace of the error, if it is provided.
+#[cfg(feature = "alloc")]
+fn provided_location_trace<'a>(e: &'a (dyn crate::Error + 'static)) -> Option<&'a LocationTrace> {
+    #[cfg(feature = "unstable-provider-api")]
+    {
+        crate::error::request_ref::<LocationTrace>(e)
+    }
+
+    #[cfg(not(feature = "unstable-provider-api"))]
+    {
+        let _e = e;
+        None
+    }
+}
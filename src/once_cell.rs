@@ -0,0 +1,36 @@
+use core::cell::UnsafeCell;
+use std::sync::Once;
+
+/// A value that is initialized the first time it is used, for
+/// versions of Rust without `std::sync::OnceLock`.
+pub struct OnceCell<T> {
+    start: Once,
+    value: UnsafeCell<Option<T>>,
+}
+
+// Like `OnceLock`, the value may be initialized by one thread and
+// then shared with others.
+unsafe impl<T: Send + Sync> Sync for OnceCell<T> {}
+
+impl<T> OnceCell<T> {
+    pub const fn new() -> Self {
+        Self {
+            start: Once::new(),
+            value: UnsafeCell::new(None),
+        }
+    }
+
+    pub fn get_or_init<F: FnOnce() -> T>(&self, f: F) -> &T {
+        self.start.call_once(|| {
+            let value = f();
+            // SAFETY: `call_once` runs this closure at most once and
+            // no references to the value exist before it completes.
+            unsafe { *self.value.get() = Some(value) };
+        });
+
+        // SAFETY: `call_once` has completed, so the value is never
+        // written again and may be shared.
+        let value = unsafe { &*self.value.get() };
+        value.as_ref().expect("The value was not initialized")
+    }
+}
@@ -0,0 +1,43 @@
+//! Support for comparing source errors in errors that use
+//! `#[snafu(partial_eq)]`.
+//!
+//! Source errors are compared with [`PartialEq`] when the source type
+//! implements it, falling back to comparing their [`Display`]
+//! output. The choice is made by method resolution: the preferred
+//! implementation is on `SourceEq` and the fallback is on
+//! `&SourceEq`, so calling `(&SourceEq(a, b)).source_eq()` picks the
+//! first one that applies.
+
+#[cfg(any(feature = "alloc", test))]
+use core::fmt::Display;
+
+pub struct SourceEq<'a, T: ?Sized>(pub &'a T, pub &'a T);
+
+pub trait ByPartialEq {
+    fn source_eq(&self) -> bool;
+}
+
+impl<T> ByPartialEq for SourceEq<'_, T>
+where
+    T: PartialEq + ?Sized,
+{
+    fn source_eq(&self) -> bool {
+        self.0 == self.1
+    }
+}
+
+pub trait ByDisplay {
+    fn source_eq(&self) -> bool;
+}
+
+#[cfg(any(feature = "alloc", test))]
+impl<T> ByDisplay for &SourceEq<'_, T>
+where
+    T: Display + ?Sized,
+{
+    fn source_eq(&self) -> bool {
+        use alloc::string::ToString;
+
+        self.0.to_string() == self.1.to_string()
+    }
+}
@@ -0,0 +1,128 @@
+//! Letting [`Report`][crate::Report] inspect errors on stable Rust.
+//!
+//! Without the [`unstable-provider-api` feature flag][provider-ff],
+//! a [`Report`][crate::Report] only sees the sources of its error as
+//! `dyn Error` and cannot ask them for a [`Location`]. Registering
+//! an error type allows the report to downcast to it and use its
+//! [`ErrorCompat`][] implementation instead.
+//!
+//! ```rust
+//! use snafu::{prelude::*, IntoError, Location, Report};
+//!
+//! #[derive(Debug, Snafu)]
+//! #[snafu(display("The configuration is missing"))]
+//! struct ConfigError {
+//!     #[snafu(implicit)]
+//!     location: Location,
+//! }
+//!
+//! #[derive(Debug, Snafu)]
+//! #[snafu(display("Could not start"))]
+//! struct StartupError {
+//!     source: ConfigError,
+//! }
+//!
+//! snafu::registry::register::<ConfigError>();
+//!
+//! let report = Report::from_error(StartupSnafu.into_error(ConfigSnafu.build()));
+//! assert!(report.to_string().contains("registry.rs"));
+//! ```
+//!
+//! The registration is global, so it is typically done once at the
+//! start of `main`. Registering the same type more than once has no
+//! additional effect.
+//!
+//! [provider-ff]: crate::guide::feature_flags#unstable-provider-api
+
+use crate::{ErrorCompat, Location, LocationTrace};
+use std::{any::TypeId, sync::RwLock, vec::Vec};
+
+type ErrorRef<'a> = &'a (dyn crate::Error + 'static);
+
+#[derive(Copy, Clone)]
+struct Lookups {
+    location: fn(ErrorRef<'_>) -> Option<Location>,
+    location_trace: for<'a> fn(ErrorRef<'a>) -> Option<&'a LocationTrace>,
+    display_includes_source: fn(ErrorRef<'_>) -> Option<bool>,
+}
+
+static REGISTRY: Registry<Lookups> = Registry::new();
+
+/// Allows [`Report`][crate::Report] to find the location and location
+/// trace of `E` anywhere in the error chain, and to know if its
+/// message already contains the message of its source.
+pub fn register<E>()
+where
+    E: ErrorCompat + crate::Error + 'static,
+{
+    fn location<E>(e: ErrorRef<'_>) -> Option<Location>
+    where
+        E: ErrorCompat + crate::Error + 'static,
+    {
+        e.downcast_ref::<E>()?.location()
+    }
+
+    fn location_trace<E>(e: ErrorRef<'_>) -> Option<&LocationTrace>
+    where
+        E: ErrorCompat + crate::Error + 'static,
+    {
+        e.downcast_ref::<E>()?.location_trace()
+    }
+
+    fn display_includes_source<E>(e: ErrorRef<'_>) -> Option<bool>
+    where
+        E: ErrorCompat + crate::Error + 'static,
+    {
+        e.downcast_ref::<E>()?.display_includes_source()
+    }
+
+    REGISTRY.insert::<E>(Lookups {
+        location: location::<E>,
+        location_trace: location_trace::<E>,
+        display_includes_source: display_includes_source::<E>,
+    });
+}
+
+/// The location of the error, if its type has been registered.
+pub(crate) fn location(e: ErrorRef<'_>) -> Option<Location> {
+    REGISTRY.find_map(|l| (l.location)(e))
+}
+
+/// The location trace of the error, if its type has been registered.
+pub(crate) fn location_trace(e: ErrorRef<'_>) -> Option<&LocationTrace> {
+    REGISTRY.find_map(|l| (l.location_trace)(e))
+}
+
+/// Whether the message of the error contains the message of its
+/// source, if its type has been registered and that is known.
+pub(crate) fn display_includes_source(e: ErrorRef<'_>) -> Option<bool> {
+    REGISTRY.find_map(|l| (l.display_includes_source)(e))
+}
+
+/// Functions to call on an error, keyed by the type they downcast to.
+pub(crate) struct Registry<T>(RwLock<Vec<(TypeId, T)>>);
+
+impl<T: Copy> Registry<T> {
+    pub(crate) const fn new() -> Self {
+        Self(RwLock::new(Vec::new()))
+    }
+
+    pub(crate) fn insert<E: 'static>(&self, value: T) {
+        let id = TypeId::of::<E>();
+        let mut entries = self.0.write().unwrap_or_else(|e| e.into_inner());
+
+        if entries.iter().all(|&(other, _)| other != id) {
+            entries.push((id, value));
+        }
+    }
+
+    pub(crate) fn find_map<R>(&self, f: impl FnMut(T) -> Option<R>) -> Option<R> {
+        // Copy the entries out so that `f` never runs with the lock held
+        let entries: Vec<T> = {
+            let entries = self.0.read().unwrap_or_else(|e| e.into_inner());
+            entries.iter().map(|&(_, v)| v).collect()
+        };
+
+        entries.into_iter().find_map(f)
+    }
+}
@@ -1,4 +1,7 @@
-use crate::ChainCompat;
+use crate::{i18n::ReportCatalog, ChainCompat, ErrorCompat};
+
+#[cfg(feature = "std")]
+use crate::Severity;
 use core::{fmt, panic::Location};
 
 #[cfg(feature = "std")]
@@ -39,6 +42,36 @@ use alloc::string::{String, ToString};
 ///
 /// [try-ff]: crate::guide::feature_flags#unstable-try-trait
 ///
+/// ## Exit codes
+///
+/// When returned from `main`, the process exits with the exit code
+/// provided by the error via `#[snafu(exit_code)]` when the report
+/// was created by the [`snafu::report`][] procedural macro or uses
+/// [`with_exit_code`][Self::with_exit_code]. Otherwise
+/// [`ExitCode::FAILURE`][] is used.
+///
+/// ## Locations and backtraces
+///
+/// When the report was created by the [`snafu::report`][] procedural
+/// macro or uses [`with_error_compat`][Self::with_error_compat], the
+/// error is inspected through its [`ErrorCompat`][] implementation:
+/// its [`Location`][] is appended to its message and the
+/// [`Backtrace`][] of the most deeply nested error is included in the
+/// output. Each source error whose type has been
+/// [registered][crate::registry::register] has its [`Location`][]
+/// appended to its message as well.
+/// When an error in the chain has a [`LocationTrace`][crate::LocationTrace],
+/// the locations of the first one are listed as well.
+///
+/// Frames of the backtrace from the standard library, common
+/// runtimes, and SNAFU are hidden. See [`snafu::backtrace`][crate::backtrace]
+/// for details and [`with_backtrace_filter`][Self::with_backtrace_filter]
+/// to choose other frames.
+///
+/// Redundant text is not removed from the message of a registered
+/// error using `#[snafu(display_source(never))]`, as it cannot
+/// contain the message of its source.
+///
 /// ## Interaction with the Provider API
 ///
 /// If you return a [`Report`][] from your function and enable the
@@ -49,18 +82,29 @@ use alloc::string::{String, ToString};
 ///    message.
 /// 1. If provided, a [`Backtrace`][] will be included in the output.
 /// 1. If provided, a [`ExitCode`][] will be used as the return value.
+/// 1. If provided, the most severe [`Severity`][] in the chain will be
+///    used to label the error message. When no [`ExitCode`][] is
+///    provided, the severity is converted into one.
 ///
 /// [provider-ff]: crate::guide::feature_flags#unstable-provider-api
 /// [`Location`]: crate::Location
 /// [`Backtrace`]: crate::Backtrace
 /// [`ExitCode`]: std::process::ExitCode
+/// [`Severity`]: crate::Severity
 ///
 /// ## Stability of the output
 ///
 /// The exact content and format of a displayed `Report` are not
 /// stable, but this type strives to print the error and as much
 /// user-relevant information in an easily-consumable manner
-pub struct Report<E>(Result<(), E>);
+pub struct Report<E> {
+    result: Result<(), E>,
+    catalog: ReportCatalog,
+    details: Option<fn(&E) -> ErrorDetails<'_>>,
+    backtrace_filter: ReportBacktraceFilter,
+    #[cfg(feature = "std")]
+    exit_code: Option<fn(&E) -> Option<ExitCode>>,
+}
 
 impl<E> Report<E> {
     /// Convert an error into a [`Report`][].
@@ -81,7 +125,7 @@ impl<E> Report<E> {
     /// }
     /// ```
     pub fn from_error(error: E) -> Self {
-        Self(Err(error))
+        Self::from(Err(error))
     }
 
     /// Executes a closure that returns a [`Result`][], converting any
@@ -106,18 +150,218 @@ impl<E> Report<E> {
     /// }
     /// ```
     pub fn capture(body: impl FnOnce() -> Result<(), E>) -> Self {
-        Self(body())
+        Self::from(body())
     }
 
     /// A [`Report`][] that indicates no error occurred.
     pub const fn ok() -> Self {
-        Self(Ok(()))
+        Self {
+            result: Ok(()),
+            catalog: ReportCatalog::none(),
+            details: None,
+            backtrace_filter: ReportBacktraceFilter::none(),
+            #[cfg(feature = "std")]
+            exit_code: None,
+        }
+    }
+
+    /// Localize the error messages using the provided
+    /// [`Catalog`][crate::i18n::Catalog].
+    ///
+    /// Every error in the chain that has a display key (see
+    /// [`snafu::i18n`][crate::i18n]) is rendered with the message
+    /// from the catalog. Errors without a key, or whose key is
+    /// unknown to the catalog, use their normal display text.
+    ///
+    /// ```rust
+    /// use snafu::{i18n::Args, prelude::*, Report};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// #[snafu(display(key = "placeholder", "Placeholder failed"))]
+    /// struct PlaceholderError;
+    ///
+    /// fn french(key: &str, _args: &Args<'_>) -> Option<String> {
+    ///     (key == "placeholder").then(|| "Échec de l'espace réservé".into())
+    /// }
+    ///
+    /// fn main() -> Report<PlaceholderError> {
+    ///     Report::capture(|| {
+    ///         let _v = may_fail_with_placeholder_error()?;
+    ///
+    ///         Ok(())
+    ///     })
+    ///     .with_catalog(french)
+    /// }
+    ///
+    /// fn may_fail_with_placeholder_error() -> Result<u8, PlaceholderError> {
+    ///     Ok(42)
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn with_catalog(self, catalog: impl crate::i18n::Catalog + Send + Sync + 'static) -> Self {
+        Self {
+            catalog: ReportCatalog::new(catalog),
+            ..self
+        }
+    }
+
+    /// Choose which frames of the backtrace are shown. By default,
+    /// frames are hidden using [`Filter::new`][crate::backtrace::Filter::new]
+    /// and, when the report was created by the [`snafu::report`][]
+    /// procedural macro, frames from the crate using the macro are
+    /// highlighted.
+    ///
+    /// [`snafu::report`]: macro@crate::report
+    ///
+    /// ```rust
+    /// use snafu::{backtrace::Filter, prelude::*, Report};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// struct PlaceholderError;
+    ///
+    /// fn main() -> Report<PlaceholderError> {
+    ///     Report::capture(|| {
+    ///         let _v = may_fail_with_placeholder_error()?;
+    ///
+    ///         Ok(())
+    ///     })
+    ///     .with_backtrace_filter(
+    ///         Filter::new()
+    ///             .hide("hyper::")
+    ///             .highlight(concat!(env!("CARGO_CRATE_NAME"), "::")),
+    ///     )
+    /// }
+    ///
+    /// fn may_fail_with_placeholder_error() -> Result<u8, PlaceholderError> {
+    ///     Ok(42)
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn with_backtrace_filter(self, filter: crate::backtrace::Filter) -> Self {
+        Self {
+            backtrace_filter: ReportBacktraceFilter(Some(filter)),
+            ..self
+        }
+    }
+
+    /// Highlights the frames of the crate calling the `snafu::report`
+    /// procedural macro, unless another filter has been chosen. The
+    /// crate is the first segment of the `module_path!()` of the
+    /// caller, which works without Cargo.
+    #[doc(hidden)]
+    pub fn __snafu_highlight_crate(self, module_path: &'static str) -> Self {
+        #[cfg(feature = "std")]
+        {
+            let name = module_path.split("::").next().unwrap_or(module_path);
+            match self.backtrace_filter.0 {
+                Some(_) => self,
+                None => self
+                    .with_backtrace_filter(crate::backtrace::Filter::new().highlight_crate(name)),
+            }
+        }
+
+        #[cfg(not(feature = "std"))]
+        {
+            let _module_path = module_path;
+            self
+        }
+    }
+
+    /// Find the location and location trace of the error and the
+    /// backtrace of the most deeply nested error in the chain through
+    /// its [`ErrorCompat`][] implementation, using
+    /// [`ErrorCompat::deepest_backtrace`][].
+    ///
+    /// This works on stable Rust for any error type that derives
+    /// [`Snafu`][crate::Snafu], without registering the type. The
+    /// locations of the sources of the error are still only found
+    /// when their types have been
+    /// [registered][crate::registry::register]. The
+    /// [`snafu::report`][] procedural macro calls this automatically
+    /// when the error type implements the trait.
+    ///
+    /// [`snafu::report`]: macro@crate::report
+    ///
+    /// ```rust
+    /// use snafu::{prelude::*, Location, Report};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// #[snafu(display("The configuration is missing"))]
+    /// struct ConfigError {
+    ///     #[snafu(implicit)]
+    ///     location: Location,
+    /// }
+    ///
+    /// let report = Report::from_error(ConfigSnafu.build()).with_error_compat();
+    /// assert!(report.to_string().contains("report.rs"));
+    /// ```
+    pub fn with_error_compat(self) -> Self
+    where
+        E: ErrorCompat,
+    {
+        Self {
+            details: Some(|e| ErrorDetails::from_compat(e)),
+            ..self
+        }
+    }
+
+    /// Use the exit code provided by the error's
+    /// [`ExitCodeProvider`][crate::exit_code::ExitCodeProvider]
+    /// implementation when the report terminates the process.
+    ///
+    /// This works on stable Rust. The [`snafu::report`][] procedural
+    /// macro calls this automatically when the error type implements
+    /// the trait.
+    ///
+    /// [`snafu::report`]: macro@crate::report
+    ///
+    /// ```rust,no_run
+    /// use snafu::{prelude::*, Report};
+    ///
+    /// #[derive(Debug, Snafu)]
+    /// #[snafu(display("The configuration is invalid"), exit_code = 78)]
+    /// struct ConfigError;
+    ///
+    /// fn main() -> Report<ConfigError> {
+    ///     // Exits with code 78
+    ///     Report::capture(|| ConfigSnafu.fail()).with_exit_code()
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn with_exit_code(self) -> Self
+    where
+        E: crate::exit_code::ExitCodeProvider,
+    {
+        Self {
+            exit_code: Some(|e| e.exit_code()),
+            ..self
+        }
     }
 }
 
 impl<E> From<Result<(), E>> for Report<E> {
     fn from(other: Result<(), E>) -> Self {
-        Self(other)
+        Self {
+            result: other,
+            catalog: ReportCatalog::none(),
+            details: None,
+            backtrace_filter: ReportBacktraceFilter::none(),
+            #[cfg(feature = "std")]
+            exit_code: None,
+        }
+    }
+}
+
+impl<E> Report<E>
+where
+    E: crate::Error,
+{
+    fn formatter<'a>(&'a self, e: &'a E) -> ReportFormatter<'a> {
+        ReportFormatter {
+            error: e,
+            details: self.details.map(|details| details(e)).unwrap_or_default(),
+            backtrace_filter: &self.backtrace_filter,
+        }
     }
 }
 
@@ -135,8 +379,11 @@ where
     E: crate::Error,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match &self.0 {
-            Err(e) => fmt::Display::fmt(&ReportFormatter(e), f),
+        match &self.result {
+            Err(e) => {
+                let formatter = self.formatter(e);
+                self.catalog.scope(|| fmt::Display::fmt(&formatter, f))
+            }
             _ => Ok(()),
         }
     }
@@ -148,40 +395,54 @@ where
     E: crate::Error,
 {
     fn report(self) -> ExitCode {
-        match self.0 {
+        match &self.result {
             Ok(()) => ExitCode::SUCCESS,
             Err(e) => {
-                std::eprintln!("Error: {}", ReportFormatter(&e));
+                let severity = request_severity(e);
+                let label = severity.unwrap_or(Severity::Error);
+
+                let formatter = self.formatter(e);
+                self.catalog
+                    .scope(|| std::eprintln!("{}: {}", label, formatter));
+
+                self.exit_code
+                    .and_then(|exit_code| exit_code(e))
+                    .or_else(|| request_exit_code(e))
+                    .or_else(|| severity.map(ExitCode::from))
+                    .unwrap_or(ExitCode::FAILURE)
+            }
+        }
+    }
+}
 
-                #[cfg(feature = "unstable-provider-api")]
-                {
-                    use crate::error;
+/// The first [`ExitCode`][] provided by any error in the chain.
+#[cfg(feature = "std")]
+fn request_exit_code(e: &dyn crate::Error) -> Option<ExitCode> {
+    #[cfg(feature = "unstable-provider-api")]
+    {
+        use crate::error;
 
-                    ChainCompat::new(&e)
-                        .find_map(|e| {
-                            error::request_value(e).or_else(|| error::request_ref(e).copied())
-                        })
-                        .unwrap_or(ExitCode::FAILURE)
-                }
+        ChainCompat::new(e)
+            .find_map(|e| error::request_value(e).or_else(|| error::request_ref(e).copied()))
+    }
 
-                #[cfg(not(feature = "unstable-provider-api"))]
-                {
-                    ExitCode::FAILURE
-                }
-            }
-        }
+    #[cfg(not(feature = "unstable-provider-api"))]
+    {
+        let _e = e;
+        None
     }
 }
 
-fn request_location(e: &dyn crate::Error) -> Option<&Location<'static>> {
+/// The most severe [`Severity`][] provided by any error in the chain.
+#[cfg(feature = "std")]
+fn request_severity(e: &dyn crate::Error) -> Option<Severity> {
     #[cfg(feature = "unstable-provider-api")]
     {
         use crate::error;
 
-        error::request_ref::<&'static Location>(e)
-            .copied()
-            .or_else(|| error::request_ref::<Location>(e))
-            .or_else(|| error::request_value::<&'static Location>(e))
+        ChainCompat::new(e)
+            .filter_map(error::request_value::<Severity>)
+            .max()
     }
 
     #[cfg(not(feature = "unstable-provider-api"))]
@@ -191,14 +452,98 @@ fn request_location(e: &dyn crate::Error) -> Option<&Location<'static>> {
     }
 }
 
+/// What is known about an error in the chain beyond its message.
+#[derive(Copy, Clone, Default)]
+struct ErrorDetails<'a> {
+    location: Option<&'a Location<'static>>,
+    location_trace: Option<&'a crate::LocationTrace>,
+    backtrace: Option<&'a crate::Backtrace>,
+    display_includes_source: Option<bool>,
+}
+
+impl<'a> ErrorDetails<'a> {
+    fn from_compat<E>(e: &'a E) -> Self
+    where
+        E: ErrorCompat,
+    {
+        Self {
+            location: e.location(),
+            location_trace: e.location_trace(),
+            backtrace: e.deepest_backtrace(),
+            display_includes_source: e.display_includes_source(),
+        }
+    }
+
+    /// The details provided by the error through the provider API.
+    fn provided(e: &'a dyn crate::Error) -> Self {
+        #[cfg(feature = "unstable-provider-api")]
+        {
+            use crate::error;
+
+            Self {
+                location: error::request_ref::<&'static Location>(e)
+                    .copied()
+                    .or_else(|| error::request_ref::<Location>(e))
+                    .or_else(|| error::request_value::<&'static Location>(e)),
+                location_trace: error::request_ref(e),
+                backtrace: error::request_ref(e),
+                display_includes_source: None,
+            }
+        }
+
+        #[cfg(not(feature = "unstable-provider-api"))]
+        {
+            let _e = e;
+            Self::default()
+        }
+    }
+
+    /// The details of the error if its type has been registered.
+    /// Only `'static` errors can be looked up.
+    fn registered(e: &'a (dyn crate::Error + 'static)) -> Self {
+        #[cfg(feature = "std")]
+        {
+            use crate::registry;
+
+            Self {
+                location: registry::location(e),
+                location_trace: registry::location_trace(e),
+                backtrace: None,
+                display_includes_source: registry::display_includes_source(e),
+            }
+        }
+
+        #[cfg(not(feature = "std"))]
+        {
+            let _e = e;
+            Self::default()
+        }
+    }
+
+    fn or(self, other: Self) -> Self {
+        Self {
+            location: self.location.or(other.location),
+            location_trace: self.location_trace.or(other.location_trace),
+            backtrace: self.backtrace.or(other.backtrace),
+            display_includes_source: self
+                .display_includes_source
+                .or(other.display_includes_source),
+        }
+    }
+}
+
 #[cfg(feature = "unstable-try-trait")]
 impl<T, E> core::ops::FromResidual<Result<T, E>> for Report<E> {
     fn from_residual(residual: Result<T, E>) -> Self {
-        Self(residual.map(drop))
+        Self::from(residual.map(drop))
     }
 }
 
-struct ReportFormatter<'a>(&'a dyn crate::Error);
+struct ReportFormatter<'a> {
+    error: &'a dyn crate::Error,
+    details: ErrorDetails<'a>,
+    backtrace_filter: &'a ReportBacktraceFilter,
+}
 
 impl<'a> fmt::Display for ReportFormatter<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -216,22 +561,94 @@ impl<'a> fmt::Display for ReportFormatter<'a> {
             self.error_trace(f)?;
         }
 
-        #[cfg(feature = "unstable-provider-api")]
-        {
-            if let Some(bt) = crate::backtraces(self.0).last() {
-                writeln!(f, "\nBacktrace:\n{}", bt)?;
+        if let Some(trace) = self.chain().find_map(|(_, d)| d.location_trace) {
+            writeln!(f, "\nLocation trace (oldest first):")?;
+            for (i, location) in trace.locations().iter().enumerate() {
+                writeln!(f, "  {}: {}", i + 1, location)?;
             }
         }
 
+        if let Some(bt) = self.chain().filter_map(|(_, d)| d.backtrace).last() {
+            writeln!(f, "\nBacktrace:")?;
+            self.backtrace_filter.write(bt, f)?;
+        }
+
         Ok(())
     }
 }
 
+/// The backtrace filter chosen for a report. Backtraces are not
+/// filtered without the standard library.
+#[cfg(feature = "std")]
+struct ReportBacktraceFilter(Option<crate::backtrace::Filter>);
+
+#[cfg(not(feature = "std"))]
+struct ReportBacktraceFilter(());
+
+impl ReportBacktraceFilter {
+    const fn none() -> Self {
+        #[cfg(feature = "std")]
+        {
+            Self(None)
+        }
+
+        #[cfg(not(feature = "std"))]
+        {
+            Self(())
+        }
+    }
+
+    fn write(&self, bt: &crate::Backtrace, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[cfg(feature = "std")]
+        {
+            match &self.0 {
+                Some(filter) => write!(f, "{}", filter.display(bt)),
+                None => write!(f, "{}", crate::backtrace::Filter::new().display(bt)),
+            }
+        }
+
+        #[cfg(all(not(feature = "std"), not(feature = "backtraces-impl-backtrace-crate")))]
+        {
+            writeln!(f, "{}", bt)
+        }
+
+        #[cfg(all(not(feature = "std"), feature = "backtraces-impl-backtrace-crate"))]
+        {
+            writeln!(f, "{:?}", bt)
+        }
+    }
+}
+
 impl<'a> ReportFormatter<'a> {
+    /// Each error in the chain with what is known about it. The
+    /// sources of the error are `'static` and can be looked up in
+    /// the registry.
+    fn chain(&self) -> impl Iterator<Item = (&'a dyn crate::Error, ErrorDetails<'a>)> + Clone {
+        let error = (
+            self.error,
+            ErrorDetails::provided(self.error).or(self.details),
+        );
+
+        let sources = self
+            .error
+            .source()
+            .into_iter()
+            .flat_map(ChainCompat::new)
+            .map(|e| {
+                let details = ErrorDetails::provided(e).or(ErrorDetails::registered(e));
+                (e as &dyn crate::Error, details)
+            });
+
+        core::iter::once(error).chain(sources)
+    }
+
     fn error_trace(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        writeln!(f, "{}", AddLocation(self.0))?;
+        let mut sources = self.chain();
+
+        if let Some(error) = sources.next() {
+            writeln!(f, "{}", AddLocation(error))?;
+        }
 
-        let sources = ChainCompat::new(self.0).skip(1);
         let plurality = sources.clone().take(2).count();
 
         match plurality {
@@ -257,13 +674,22 @@ impl<'a> ReportFormatter<'a> {
 
         let mut any_cleaned = false;
         let mut any_removed = false;
-        let cleaned_messages: Vec<_> = CleanedErrorText::new(self.0)
-            .flat_map(|(e, mut msg, cleaned)| {
+        // `CleanedErrorText` yields one entry per error in the chain
+        let cleaned_messages: Vec<_> = CleanedErrorText::new(self.error)
+            .zip(self.chain())
+            .flat_map(|((_, msg, cleaned), (e, details))| {
+                // There is nothing to remove when the message is
+                // known to never contain the source's message.
+                let (mut msg, cleaned) = match details.display_includes_source {
+                    Some(false) => (e.to_string(), false),
+                    _ => (msg, cleaned),
+                };
+
                 if msg.is_empty() {
                     any_removed = true;
                     None
                 } else {
-                    if let Some(l) = request_location(e) {
+                    if let Some(l) = details.location {
                         use core::fmt::Write;
                         write!(msg, " ({})", l).unwrap();
                     }
@@ -323,12 +749,13 @@ impl<'a> ReportFormatter<'a> {
     }
 }
 
-struct AddLocation<E>(E);
+struct AddLocation<'a>((&'a dyn crate::Error, ErrorDetails<'a>));
 
-impl<E: crate::Error> fmt::Display for AddLocation<E> {
+impl fmt::Display for AddLocation<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)?;
-        if let Some(l) = request_location(&self.0) {
+        let (e, details) = self.0;
+        write!(f, "{}", e)?;
+        if let Some(l) = details.location {
             write!(f, " ({})", l)?;
         }
         Ok(())
@@ -435,6 +862,84 @@ impl<'a> CleanedErrorTextStep<'a> {
     }
 }
 
+/// Calls [`Report::with_exit_code`][] from the `snafu::report`
+/// procedural macro when the error implements
+/// [`ExitCodeProvider`][crate::exit_code::ExitCodeProvider]. The
+/// choice is made by method resolution: the preferred implementation
+/// is on `&__InternalExitCode` and the fallback is on
+/// `__InternalExitCode`.
+#[doc(hidden)]
+pub struct __InternalExitCode<E>(core::marker::PhantomData<E>);
+
+impl<E> __InternalExitCode<E> {
+    pub fn new(_report: &Report<E>) -> Self {
+        Self(core::marker::PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait __InternalWithExitCode<E> {
+    fn __snafu_with_exit_code(&self) -> fn(Report<E>) -> Report<E>;
+}
+
+#[cfg(feature = "std")]
+impl<E> __InternalWithExitCode<E> for &__InternalExitCode<E>
+where
+    E: crate::exit_code::ExitCodeProvider,
+{
+    fn __snafu_with_exit_code(&self) -> fn(Report<E>) -> Report<E> {
+        Report::with_exit_code
+    }
+}
+
+#[doc(hidden)]
+pub trait __InternalWithoutExitCode<E> {
+    fn __snafu_with_exit_code(&self) -> fn(Report<E>) -> Report<E>;
+}
+
+impl<E> __InternalWithoutExitCode<E> for __InternalExitCode<E> {
+    fn __snafu_with_exit_code(&self) -> fn(Report<E>) -> Report<E> {
+        |report| report
+    }
+}
+
+/// Calls [`Report::with_error_compat`][] from the `snafu::report`
+/// procedural macro when the error implements [`ErrorCompat`][], in
+/// the same way as [`__InternalExitCode`][].
+#[doc(hidden)]
+pub struct __InternalErrorCompat<E>(core::marker::PhantomData<E>);
+
+impl<E> __InternalErrorCompat<E> {
+    pub fn new(_report: &Report<E>) -> Self {
+        Self(core::marker::PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait __InternalWithErrorCompat<E> {
+    fn __snafu_with_error_compat(&self) -> fn(Report<E>) -> Report<E>;
+}
+
+impl<E> __InternalWithErrorCompat<E> for &__InternalErrorCompat<E>
+where
+    E: ErrorCompat,
+{
+    fn __snafu_with_error_compat(&self) -> fn(Report<E>) -> Report<E> {
+        Report::with_error_compat
+    }
+}
+
+#[doc(hidden)]
+pub trait __InternalWithoutErrorCompat<E> {
+    fn __snafu_with_error_compat(&self) -> fn(Report<E>) -> Report<E>;
+}
+
+impl<E> __InternalWithoutErrorCompat<E> for __InternalErrorCompat<E> {
+    fn __snafu_with_error_compat(&self) -> fn(Report<E>) -> Report<E> {
+        |report| report
+    }
+}
+
 #[doc(hidden)]
 pub trait __InternalExtractErrorType {
     type Err;
@@ -0,0 +1,49 @@
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::process::ExitCode;
+
+/// How severe an error is.
+///
+/// Severities are ordered from least to most severe, so the most
+/// severe of several errors can be found with [`Ord::max`][].
+///
+/// Use `#[snafu(severity(...))]` to assign a severity to an error; see
+/// the [`Snafu` macro](derive@crate::Snafu#assigning-a-severity) for
+/// details.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Something unexpected happened, but the program could continue.
+    Warning,
+    /// An operation failed.
+    Error,
+    /// The program cannot continue.
+    Fatal,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Severity::Warning => "Warning",
+            Severity::Error => "Error",
+            Severity::Fatal => "Fatal",
+        };
+        f.pad(name)
+    }
+}
+
+/// Warnings and errors exit with [`ExitCode::FAILURE`][] and fatal
+/// errors exit with code 2. A program that returns an error still
+/// failed, even when the error is only a warning; provide an exit
+/// code with `#[snafu(exit_code = 0)]` and use
+/// [`Report::with_exit_code`][crate::Report::with_exit_code] to exit
+/// successfully instead.
+#[cfg(feature = "std")]
+impl From<Severity> for ExitCode {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Warning | Severity::Error => ExitCode::FAILURE,
+            Severity::Fatal => ExitCode::from(2),
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::{fmt, ops::Deref};
+
+use crate::{Backtrace, ErrorCompat, Location};
+
+/// An error that can be cheaply cloned by sharing ownership of the
+/// underlying error.
+///
+/// Many error types, such as [`std::io::Error`][] or boxed trait
+/// objects, do not implement [`Clone`][]. Wrapping them in `Shared`
+/// allows the same failure to be handed to multiple consumers.
+///
+/// `Shared` delegates [`Display`](fmt::Display), [`Debug`](fmt::Debug),
+/// [`Error::source`](crate::Error::source), and
+/// [`ErrorCompat::backtrace`] to the underlying error, so reports
+/// that include it are identical to reports of the underlying error.
+///
+/// This type is most commonly used as the type of a source field in
+/// an error that has the
+/// [`#[snafu(clone)]`](crate::Snafu#cloning-errors) attribute.
+///
+/// ## Examples
+///
+/// ```rust
+/// use snafu::Shared;
+/// use std::io;
+///
+/// let error = Shared::new(io::Error::new(io::ErrorKind::Other, "disk full"));
+/// let waiters = vec![error.clone(), error.clone()];
+///
+/// for waiter in waiters {
+///     assert_eq!(waiter.to_string(), "disk full");
+/// }
+/// ```
+pub struct Shared<E: ?Sized>(Arc<E>);
+
+impl<E> Shared<E> {
+    /// Wraps the error, allowing it to be cloned.
+    pub fn new(error: E) -> Self {
+        Self(Arc::new(error))
+    }
+}
+
+impl<E: ?Sized> Shared<E> {
+    /// Returns a reference to the underlying shared pointer.
+    pub fn as_arc(&self) -> &Arc<E> {
+        &self.0
+    }
+
+    /// Returns the underlying shared pointer.
+    pub fn into_arc(self) -> Arc<E> {
+        self.0
+    }
+}
+
+impl<E: ?Sized> Clone for Shared<E> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<E: ?Sized> Deref for Shared<E> {
+    type Target = E;
+
+    fn deref(&self) -> &E {
+        &self.0
+    }
+}
+
+impl<E: ?Sized> AsRef<E> for Shared<E> {
+    fn as_ref(&self) -> &E {
+        &self.0
+    }
+}
+
+impl<E> From<E> for Shared<E> {
+    fn from(error: E) -> Self {
+        Self::new(error)
+    }
+}
+
+impl<E: ?Sized> From<Box<E>> for Shared<E> {
+    fn from(error: Box<E>) -> Self {
+        Self(Arc::from(error))
+    }
+}
+
+impl<E: ?Sized> From<Arc<E>> for Shared<E> {
+    fn from(error: Arc<E>) -> Self {
+        Self(error)
+    }
+}
+
+impl<E> fmt::Debug for Shared<E>
+where
+    E: fmt::Debug + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
+
+impl<E> fmt::Display for Shared<E>
+where
+    E: fmt::Display + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&*self.0, f)
+    }
+}
+
+impl<E> crate::Error for Shared<E>
+where
+    E: crate::Error + ?Sized,
+{
+    #[allow(deprecated)]
+    fn description(&self) -> &str {
+        self.0.description()
+    }
+
+    #[allow(deprecated)]
+    fn cause(&self) -> Option<&dyn crate::Error> {
+        self.0.cause()
+    }
+
+    fn source(&self) -> Option<&(dyn crate::Error + 'static)> {
+        self.0.source()
+    }
+
+    #[cfg(feature = "unstable-provider-api")]
+    fn provide<'a>(&'a self, request: &mut crate::error::Request<'a>) {
+        self.0.provide(request)
+    }
+}
+
+impl<E> ErrorCompat for Shared<E>
+where
+    E: ErrorCompat + ?Sized,
+{
+    fn backtrace(&self) -> Option<&Backtrace> {
+        self.0.backtrace()
+    }
+
+    fn location(&self) -> Option<Location> {
+        self.0.location()
+    }
+
+    fn location_trace(&self) -> Option<&crate::LocationTrace> {
+        self.0.location_trace()
+    }
+
+    fn display_includes_source(&self) -> Option<bool> {
+        self.0.display_includes_source()
+    }
+
+    fn deepest_backtrace(&self) -> Option<&Backtrace> {
+        self.0.deepest_backtrace()
+    }
+}
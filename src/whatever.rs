@@ -1,6 +1,10 @@
-use alloc::{boxed::Box, string::String};
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+};
+use core::{convert::TryFrom, fmt};
 
-use crate::{Backtrace, ChainCompat, Snafu};
+use crate::{AsErrorSource, Backtrace, ChainCompat, Snafu};
 
 /// A basic error type that you can use as a first step to better
 /// error handling.
@@ -71,6 +75,99 @@ impl Whatever {
     pub fn backtrace(&self) -> &Backtrace {
         known_whatevers_backtrace(self).unwrap_or(&self.backtrace)
     }
+
+    /// The message of this error, without the messages of its
+    /// sources.
+    ///
+    /// ```rust
+    /// use snafu::{prelude::*, Whatever};
+    ///
+    /// let error: Whatever = "nope"
+    ///     .parse::<u8>()
+    ///     .whatever_context("Could not parse the port")
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(error.message(), "Could not parse the port");
+    /// ```
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The error wrapped by this error, if any.
+    pub fn source_ref(&self) -> Option<&(dyn crate::Error + Send + Sync + 'static)> {
+        self.source.as_deref()
+    }
+
+    /// The first error of type `T` in the chain of sources, starting
+    /// with the error wrapped by this error.
+    ///
+    /// ```rust
+    /// use snafu::{prelude::*, Whatever};
+    /// use std::num::ParseIntError;
+    ///
+    /// fn parse(port: &str) -> Result<u16, Whatever> {
+    ///     port.parse().whatever_context("Could not parse the port")
+    /// }
+    ///
+    /// fn load() -> Result<u16, Whatever> {
+    ///     parse("nope").whatever_context("Could not load the config")
+    /// }
+    ///
+    /// let error = load().unwrap_err();
+    ///
+    /// assert!(error.downcast_source_ref::<ParseIntError>().is_some());
+    /// assert!(error.downcast_source_ref::<std::io::Error>().is_none());
+    /// ```
+    pub fn downcast_source_ref<T>(&self) -> Option<&T>
+    where
+        T: crate::Error + 'static,
+    {
+        downcast_source_ref(self)
+    }
+
+    /// Consumes this error, returning the error it wraps, if any.
+    pub fn into_source(self) -> Option<Box<dyn crate::Error + Send + Sync>> {
+        self.source
+    }
+
+    /// The messages of this error and each of its sources, starting
+    /// with this error.
+    ///
+    /// ```rust
+    /// use snafu::{prelude::*, Whatever};
+    ///
+    /// let error: Whatever = "nope"
+    ///     .parse::<u8>()
+    ///     .whatever_context("Could not parse the port")
+    ///     .unwrap_err();
+    ///
+    /// let messages: Vec<_> = error.chain_messages().collect();
+    /// assert_eq!(
+    ///     messages,
+    ///     ["Could not parse the port", "invalid digit found in string"],
+    /// );
+    /// ```
+    pub fn chain_messages(&self) -> impl Iterator<Item = String> + '_ {
+        chain_messages(self)
+    }
+
+    /// Converts this error into one that can be cheaply cloned.
+    ///
+    /// The returned error has the same message, sources, and
+    /// backtrace as this error.
+    ///
+    /// ```rust
+    /// use snafu::{FromString, Whatever};
+    ///
+    /// let error = Whatever::without_source("Cache fill failed".into()).shared();
+    /// let for_waiter = error.clone();
+    ///
+    /// assert_eq!(error.to_string(), for_waiter.to_string());
+    /// ```
+    #[cfg(target_has_atomic = "ptr")]
+    pub fn shared(self) -> crate::Shared<Self> {
+        crate::Shared::new(self)
+    }
 }
 
 /// A basic error type that you can use as a first step to better
@@ -86,8 +183,8 @@ impl Whatever {
 #[snafu(whatever)]
 #[snafu(display("{message}"))]
 pub struct WhateverLocal {
-    #[snafu(source(from(Box<dyn crate::Error>, Some)))]
-    source: Option<Box<dyn crate::Error>>,
+    #[snafu(source(from(Box<dyn crate::Error>, LocalSource::local)))]
+    source: Option<LocalSource>,
     message: String,
     backtrace: Backtrace,
 }
@@ -100,6 +197,147 @@ impl WhateverLocal {
     pub fn backtrace(&self) -> &Backtrace {
         known_whatevers_backtrace(self).unwrap_or(&self.backtrace)
     }
+
+    /// The message of this error, without the messages of its
+    /// sources.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The error wrapped by this error, if any.
+    pub fn source_ref(&self) -> Option<&(dyn crate::Error + 'static)> {
+        self.source.as_ref().map(|e| e.as_error_source())
+    }
+
+    /// The first error of type `T` in the chain of sources, starting
+    /// with the error wrapped by this error.
+    pub fn downcast_source_ref<T>(&self) -> Option<&T>
+    where
+        T: crate::Error + 'static,
+    {
+        downcast_source_ref(self)
+    }
+
+    /// Consumes this error, returning the error it wraps, if any.
+    pub fn into_source(self) -> Option<Box<dyn crate::Error>> {
+        self.source.map(|e| match e {
+            LocalSource::Local(e) => e,
+            LocalSource::SendSync(e) => e,
+        })
+    }
+
+    /// The messages of this error and each of its sources, starting
+    /// with this error.
+    pub fn chain_messages(&self) -> impl Iterator<Item = String> + '_ {
+        chain_messages(self)
+    }
+}
+
+impl From<Whatever> for WhateverLocal {
+    fn from(other: Whatever) -> Self {
+        let Whatever {
+            source,
+            message,
+            backtrace,
+        } = other;
+
+        Self {
+            source: source.map(LocalSource::SendSync),
+            message,
+            backtrace,
+        }
+    }
+}
+
+/// Converts back into a [`Whatever`][] when the source, if any, is
+/// known to be [`Send`][] and [`Sync`][]. This is the case when it
+/// has no source or when it was created from a `Whatever`.
+///
+/// ```rust
+/// use snafu::{prelude::*, Whatever, WhateverLocal};
+/// use std::convert::TryFrom;
+///
+/// let error: Whatever = "nope"
+///     .parse::<u8>()
+///     .whatever_context("Could not parse the port")
+///     .unwrap_err();
+///
+/// let local = WhateverLocal::from(error);
+/// let error = Whatever::try_from(local).unwrap();
+/// assert_eq!(error.message(), "Could not parse the port");
+/// ```
+impl TryFrom<WhateverLocal> for Whatever {
+    type Error = WhateverLocal;
+
+    fn try_from(other: WhateverLocal) -> Result<Self, Self::Error> {
+        let WhateverLocal {
+            source,
+            message,
+            backtrace,
+        } = other;
+
+        let source = match source {
+            None => None,
+            Some(LocalSource::SendSync(e)) => Some(e),
+            Some(source @ LocalSource::Local(_)) => {
+                return Err(WhateverLocal {
+                    source: Some(source),
+                    message,
+                    backtrace,
+                })
+            }
+        };
+
+        Ok(Self {
+            source,
+            message,
+            backtrace,
+        })
+    }
+}
+
+/// The source of a [`WhateverLocal`][], which remembers if it came
+/// from a [`Whatever`][] so that it can be converted back.
+enum LocalSource {
+    Local(Box<dyn crate::Error>),
+    SendSync(Box<dyn crate::Error + Send + Sync>),
+}
+
+impl LocalSource {
+    fn local(e: Box<dyn crate::Error>) -> Option<Self> {
+        Some(Self::Local(e))
+    }
+}
+
+impl fmt::Debug for LocalSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Local(e) => fmt::Debug::fmt(e, f),
+            Self::SendSync(e) => fmt::Debug::fmt(e, f),
+        }
+    }
+}
+
+impl AsErrorSource for LocalSource {
+    fn as_error_source(&self) -> &(dyn crate::Error + 'static) {
+        match self {
+            Self::Local(e) => &**e,
+            Self::SendSync(e) => &**e,
+        }
+    }
+}
+
+fn downcast_source_ref<'a, T>(root: &'a (dyn crate::Error + 'static)) -> Option<&'a T>
+where
+    T: crate::Error + 'static,
+{
+    ChainCompat::new(root)
+        .skip(1)
+        .find_map(|e| e.downcast_ref::<T>())
+}
+
+fn chain_messages<'a>(root: &'a (dyn crate::Error + 'static)) -> impl Iterator<Item = String> + 'a {
+    ChainCompat::new(root).map(|e| e.to_string())
 }
 
 fn known_whatevers_backtrace<'a>(
@@ -0,0 +1,14 @@
+use snafu::{prelude::*, Backtrace, ErrorCompat};
+
+#[derive(Debug, Snafu)]
+enum Error {
+    BacktraceSometimes { backtrace: Option<Backtrace> },
+}
+
+#[test]
+fn optional_backtrace_is_absent_when_environment_variable_is_zero() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "0");
+    std::env::set_var("RUST_BACKTRACE", "1");
+    let sometimes = BacktraceSometimesSnafu.build();
+    assert!(ErrorCompat::backtrace(&sometimes).is_none());
+}
@@ -252,3 +252,154 @@ mod boxed {
         assert!(text.contains("::arc_is_backtrace"));
     }
 }
+
+mod deepest {
+    use snafu::{prelude::*, Backtrace, ErrorCompat, IntoError, Report};
+
+    #[derive(Debug, Snafu)]
+    struct LeafError {
+        backtrace: Backtrace,
+    }
+
+    #[derive(Debug, Snafu)]
+    struct NoBacktraceError {
+        source: LeafError,
+    }
+
+    #[derive(Debug, Snafu)]
+    struct OpaqueError(NoBacktraceError);
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        Boxed {
+            source: Box<OpaqueError>,
+            backtrace: Backtrace,
+        },
+
+        Foreign {
+            source: std::io::Error,
+            backtrace: Backtrace,
+        },
+    }
+
+    fn leaf() -> Result<(), LeafError> {
+        LeafSnafu.fail()
+    }
+
+    fn middle() -> Result<(), OpaqueError> {
+        leaf().context(NoBacktraceSnafu)?;
+        Ok(())
+    }
+
+    fn outer() -> Result<(), Error> {
+        middle().map_err(Box::new).context(BoxedSnafu)
+    }
+
+    #[test]
+    fn backtrace_comes_from_the_deepest_error() {
+        let e = outer().unwrap_err();
+
+        let text = e
+            .deepest_backtrace()
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        assert!(
+            text.contains("::leaf"),
+            "{:?} does not contain `::leaf`",
+            text
+        );
+
+        let text = ErrorCompat::backtrace(&e)
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        assert!(!text.contains("::leaf"), "{:?} contains `::leaf`", text);
+    }
+
+    #[test]
+    fn chain_stops_at_errors_without_error_compat() {
+        let e = ForeignSnafu.into_error(std::io::ErrorKind::Other.into());
+
+        let deepest = e.deepest_backtrace().map(|b| b as *const Backtrace);
+        let own = ErrorCompat::backtrace(&e).map(|b| b as *const Backtrace);
+        assert!(deepest.is_some());
+        assert_eq!(deepest, own);
+    }
+
+    #[test]
+    fn report_uses_the_deepest_backtrace() {
+        let text = Report::from_error(outer().unwrap_err())
+            .with_error_compat()
+            .to_string();
+        assert!(
+            text.contains("::leaf"),
+            "{:?} does not contain `::leaf`",
+            text
+        );
+    }
+}
+
+mod lazy {
+    use snafu::{prelude::*, ErrorCompat, LazyBacktrace, Report};
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        Named {
+            backtrace: LazyBacktrace,
+        },
+
+        Renamed {
+            #[snafu(backtrace)]
+            trace: LazyBacktrace,
+        },
+
+        Implicit {
+            #[snafu(implicit)]
+            captured: LazyBacktrace,
+        },
+    }
+
+    fn named() -> Result<(), Error> {
+        NamedSnafu.fail()
+    }
+
+    #[test]
+    fn backtrace_contains_function_names() {
+        let e = named().unwrap_err();
+        let text = ErrorCompat::backtrace(&e)
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        assert!(
+            text.contains("::named"),
+            "{:?} does not contain `::named`",
+            text
+        );
+
+        let e = RenamedSnafu.build();
+        assert!(ErrorCompat::backtrace(&e).is_some());
+    }
+
+    #[test]
+    fn implicit_field_is_captured() {
+        let e = ImplicitSnafu.build();
+        assert!(ErrorCompat::backtrace(&e).is_none());
+
+        let Error::Implicit { captured } = e else {
+            unreachable!()
+        };
+        assert!(captured
+            .to_string()
+            .contains("::implicit_field_is_captured"));
+    }
+
+    #[test]
+    fn report_includes_the_backtrace() {
+        let text = Report::from_error(named().unwrap_err())
+            .with_error_compat()
+            .to_string();
+        assert!(
+            text.contains("::named"),
+            "{:?} does not contain `::named`",
+            text
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use snafu::{
+    backtrace::{self, Policy},
+    prelude::*,
+    Backtrace, ErrorCompat,
+};
+
+#[derive(Debug, Snafu)]
+struct OptionalError {
+    backtrace: Option<Backtrace>,
+}
+
+#[derive(Debug, Snafu)]
+struct RequiredError {
+    backtrace: Backtrace,
+}
+
+fn captured(n: usize) -> usize {
+    (0..n)
+        .filter(|_| ErrorCompat::backtrace(&OptionalSnafu.build()).is_some())
+        .count()
+}
+
+// The policy is global, so every change is made in the same test.
+#[test]
+fn policy_can_be_changed_at_runtime() {
+    assert_eq!(backtrace::capture(), Policy::Env);
+
+    backtrace::set_capture(Policy::Always);
+    assert_eq!(backtrace::capture(), Policy::Always);
+    assert_eq!(captured(4), 4);
+
+    backtrace::set_capture(Policy::Never);
+    assert_eq!(backtrace::capture(), Policy::Never);
+    assert_eq!(captured(4), 0);
+    assert!(ErrorCompat::backtrace(&RequiredSnafu.build()).is_some());
+
+    backtrace::set_capture(Policy::Sampled(3));
+    assert_eq!(backtrace::capture(), Policy::Sampled(3));
+    assert_eq!(captured(9), 3);
+
+    backtrace::set_capture(Policy::Sampled(1));
+    assert_eq!(backtrace::capture(), Policy::Always);
+
+    backtrace::set_capture(Policy::Sampled(0));
+    assert_eq!(backtrace::capture(), Policy::Never);
+
+    backtrace::set_capture(Policy::Env);
+    assert_eq!(backtrace::capture(), Policy::Env);
+}
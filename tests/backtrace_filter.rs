@@ -0,0 +1,115 @@
+use snafu::{backtrace::Filter, prelude::*, Backtrace, Report};
+
+#[derive(Debug, Snafu)]
+struct LeafError {
+    backtrace: Backtrace,
+}
+
+fn leaf() -> Result<(), LeafError> {
+    LeafSnafu.fail()
+}
+
+fn report(filter: Option<Filter>) -> String {
+    let report = Report::from_error(leaf().unwrap_err()).with_error_compat();
+    let report = match filter {
+        Some(filter) => report.with_backtrace_filter(filter),
+        None => report,
+    };
+    let report = report.to_string();
+
+    let (_, backtrace) = report.split_once("Backtrace:\n").unwrap();
+    backtrace.to_owned()
+}
+
+fn functions(backtrace: &str) -> Vec<&str> {
+    backtrace
+        .lines()
+        .filter_map(|l| l.trim_start_matches('>').trim_start().split_once(": "))
+        .map(|(_, function)| function)
+        .collect()
+}
+
+#[test]
+fn library_frames_are_hidden_by_default() {
+    let backtrace = report(None);
+
+    assert!(backtrace.contains("frames hidden"), "{}", backtrace);
+    assert!(
+        functions(&backtrace)
+            .iter()
+            .all(|f| !f.starts_with("std::") && !f.starts_with("<std::")),
+        "{}",
+        backtrace,
+    );
+}
+
+fn leaf_line(backtrace: &str) -> &str {
+    backtrace
+        .lines()
+        .find(|l| l.contains("backtrace_filter::leaf"))
+        .unwrap()
+}
+
+#[test]
+fn frames_from_the_crate_using_the_procedural_macro_are_highlighted() {
+    #[snafu::report]
+    fn mainlike() -> Result<(), LeafError> {
+        leaf()
+    }
+
+    let report = mainlike().to_string();
+    let (_, backtrace) = report.split_once("Backtrace:\n").unwrap();
+    assert!(leaf_line(backtrace).starts_with('>'), "{}", backtrace);
+}
+
+#[test]
+fn frames_are_highlighted_by_crate_name() {
+    let backtrace = report(None);
+    assert!(!leaf_line(&backtrace).starts_with('>'), "{}", backtrace);
+
+    let backtrace = report(Some(Filter::new().highlight("backtrace_filter::")));
+    assert!(leaf_line(&backtrace).starts_with('>'), "{}", backtrace);
+}
+
+#[test]
+fn consecutive_hidden_frames_are_collapsed() {
+    let backtrace = report(None);
+
+    let hidden: Vec<_> = backtrace.lines().filter(|l| l.contains("hidden")).collect();
+    let mut pairs = backtrace.lines().zip(backtrace.lines().skip(1));
+    assert!(!hidden.is_empty());
+    assert!(
+        !pairs.any(|(a, b)| a.contains("hidden") && b.contains("hidden")),
+        "{}",
+        backtrace,
+    );
+}
+
+#[test]
+fn custom_rules_are_used() {
+    let backtrace = report(Some(Filter::empty()));
+    assert!(!backtrace.contains("frames hidden"), "{}", backtrace);
+    assert!(
+        functions(&backtrace).iter().any(|f| f.contains("std::")),
+        "{}",
+        backtrace,
+    );
+
+    let backtrace = report(Some(Filter::new().hide("backtrace_filter::")));
+    assert!(
+        !backtrace.contains("backtrace_filter::leaf"),
+        "{}",
+        backtrace
+    );
+
+    let backtrace = report(Some(
+        Filter::new()
+            .hide("backtrace_filter::")
+            .show("backtrace_filter::leaf"),
+    ));
+    assert!(
+        backtrace.contains("backtrace_filter::leaf"),
+        "{}",
+        backtrace
+    );
+}
@@ -0,0 +1,17 @@
+use snafu::{prelude::*, Backtrace, Report};
+
+#[derive(Debug, Snafu)]
+struct LeafError {
+    backtrace: Backtrace,
+}
+
+#[test]
+fn every_frame_is_shown_with_environment_variable() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "full");
+
+    let report = Report::from_error(LeafSnafu.build())
+        .with_error_compat()
+        .to_string();
+    assert!(!report.contains("frames hidden"), "{}", report);
+    assert!(!report.contains("\n>"), "{}", report);
+}
@@ -0,0 +1,88 @@
+use snafu::{
+    backtrace::{self, OptionalBacktrace, Policy},
+    prelude::*,
+    Backtrace, ErrorCompat, Location,
+};
+use std::time::Duration;
+
+#[derive(Debug, Snafu)]
+struct FloodError {
+    backtrace: OptionalBacktrace,
+    #[snafu(implicit)]
+    location: Location,
+}
+
+#[derive(Debug, Snafu)]
+struct PlainError {
+    backtrace: Option<Backtrace>,
+}
+
+fn flood() -> FloodError {
+    FloodSnafu.build()
+}
+
+fn other_site() -> FloodError {
+    FloodSnafu.build()
+}
+
+fn plain() -> PlainError {
+    PlainSnafu.build()
+}
+
+fn captured<E: ErrorCompat>(errors: &[E]) -> usize {
+    errors
+        .iter()
+        .filter(|e| ErrorCompat::backtrace(*e).is_some())
+        .count()
+}
+
+// The policy is global, so every change is made in the same test.
+#[test]
+fn captures_are_limited_for_each_location() {
+    let policy = Policy::RateLimited {
+        limit: 2,
+        window: Duration::from_secs(3600),
+    };
+    backtrace::set_capture(policy);
+    assert_eq!(backtrace::capture(), policy);
+
+    let errors: Vec<_> = (0..5).map(|_| flood()).collect();
+    assert_eq!(captured(&errors), 2);
+    assert!(matches!(
+        errors[0].backtrace,
+        OptionalBacktrace::Captured(_)
+    ));
+    assert!(matches!(
+        errors[4].backtrace,
+        OptionalBacktrace::RateLimited(location) if location == errors[4].location,
+    ));
+
+    let other = other_site();
+    assert!(matches!(other.backtrace, OptionalBacktrace::Captured(_)));
+
+    let errors: Vec<_> = (0..5).map(|_| plain()).collect();
+    assert_eq!(captured(&errors), 2);
+
+    backtrace::set_capture(policy);
+    let errors: Vec<_> = (0..3).map(|_| flood()).collect();
+    assert_eq!(captured(&errors), 2, "Setting the policy resets the limit");
+
+    backtrace::set_capture(Policy::RateLimited {
+        limit: 1,
+        window: Duration::ZERO,
+    });
+    let errors: Vec<_> = (0..3).map(|_| flood()).collect();
+    assert_eq!(captured(&errors), 3, "A zero window does not limit captures");
+
+    backtrace::set_capture(Policy::RateLimited {
+        limit: 0,
+        window: Duration::ZERO,
+    });
+    let errors: Vec<_> = (0..3).map(|_| flood()).collect();
+    assert_eq!(captured(&errors), 0, "A zero limit prevents every capture");
+
+    backtrace::set_capture(Policy::Never);
+    assert!(matches!(flood().backtrace, OptionalBacktrace::NotCaptured));
+
+    backtrace::set_capture(Policy::Env);
+}
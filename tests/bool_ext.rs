@@ -0,0 +1,111 @@
+use snafu::{prelude::*, Expected, IntoError, Location, NoneError, Whatever};
+use std::collections::HashMap;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("User {user_id} is not an admin"))]
+    NotAdmin { user_id: i32 },
+
+    #[snafu(display("No users"))]
+    NoUsers {
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    #[snafu(display("Missing {what} for {key}"))]
+    Missing {
+        key: String,
+        #[snafu(implicit)]
+        what: Expected,
+    },
+}
+
+#[test]
+fn context_fails_when_false() {
+    assert!(true.context(NotAdminSnafu { user_id: 1 }).is_ok());
+
+    let e = false.context(NotAdminSnafu { user_id: 1 }).unwrap_err();
+    assert_eq!(e.to_string(), "User 1 is not an admin");
+}
+
+#[test]
+fn with_context_is_only_called_when_false() {
+    let r: Result<(), Error> =
+        true.with_context(|| -> NotAdminSnafu<i32> { panic!("This block will not be evaluated") });
+    assert!(r.is_ok());
+
+    let e = false
+        .with_context(|| NotAdminSnafu { user_id: 2 })
+        .unwrap_err();
+    assert_eq!(e.to_string(), "User 2 is not an admin");
+}
+
+#[test]
+fn then_fail_fails_when_true() {
+    fn check(users: &[&str]) -> Result<(), Error> {
+        users.is_empty().then_fail(NoUsersSnafu)?;
+        Ok(())
+    }
+
+    assert!(check(&["alice"]).is_ok());
+
+    let e = check(&[]).unwrap_err();
+    let line = line!() - 7;
+    match e {
+        Error::NoUsers { location } => assert_eq!(location.line(), line),
+        _ => panic!("Unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn whatever_context_fails_when_false() {
+    assert!(true.whatever_context::<_, Whatever>("unused").is_ok());
+
+    let e: Whatever = false.whatever_context("too many retries").unwrap_err();
+    assert_eq!(e.to_string(), "too many retries");
+
+    let e: Whatever = false
+        .with_whatever_context(|| format!("{} retries", 5))
+        .unwrap_err();
+    assert_eq!(e.to_string(), "5 retries");
+}
+
+#[test]
+fn expect_context_records_what_was_expected() {
+    let mut config = HashMap::new();
+    config.insert("port", "80");
+
+    let port = config
+        .get("port")
+        .expect_context("a port", MissingSnafu { key: "port" })
+        .unwrap();
+    assert_eq!(*port, "80");
+
+    let e = config
+        .get("host")
+        .expect_context("a host name", MissingSnafu { key: "host" })
+        .unwrap_err();
+    assert_eq!(e.to_string(), "Missing a host name for host");
+}
+
+#[test]
+fn expectation_is_empty_without_expect_context() {
+    let e = None::<()>
+        .context(MissingSnafu { key: "host" })
+        .unwrap_err();
+    assert_eq!(e.to_string(), "Missing  for host");
+
+    let e = MissingSnafu { key: "host" }.into_error(NoneError);
+    assert_eq!(e.to_string(), "Missing  for host");
+}
+
+#[test]
+fn into_error_expecting_records_what_was_expected() {
+    let e = MissingSnafu { key: "host" }.into_error_expecting(NoneError, Expected("a host"));
+    assert_eq!(e.to_string(), "Missing a host for host");
+
+    match e {
+        Error::Missing { what, .. } => assert_eq!(what, Expected("a host")),
+        _ => panic!("Unexpected error: {:?}", e),
+    }
+}
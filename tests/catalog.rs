@@ -0,0 +1,188 @@
+// The errors are only described, never constructed
+#![allow(dead_code)]
+
+use snafu::{prelude::*, Backtrace, ErrorDescriptor, FieldDescriptor, Location};
+use std::{io, path::PathBuf};
+
+#[derive(Debug, Snafu)]
+#[snafu(catalog)]
+enum Error {
+    /// The file could not be opened.
+    #[snafu(display(key = "file-open", "Could not open {path:?}"))]
+    FileOpen {
+        path: PathBuf,
+        source: io::Error,
+        backtrace: Backtrace,
+    },
+
+    /// No user with the given ID exists.
+    UserMissing {
+        id: u32,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    Unit,
+
+    #[snafu(transparent)]
+    Io {
+        source: io::Error,
+    },
+
+    #[snafu(whatever, display("{message}"))]
+    Whatever {
+        message: String,
+        #[snafu(source(from(Box<dyn std::error::Error>, Some)))]
+        source: Option<Box<dyn std::error::Error>>,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(catalog, display("Limit of {}ms exceeded", limit * 1000))]
+struct TimeoutError {
+    limit: u64,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(catalog)]
+enum GenericError<T> {
+    Wrapped { value: T },
+}
+
+#[test]
+fn enum_variants_are_described() {
+    assert_eq!(Error::CATALOG.len(), 5);
+
+    assert_eq!(
+        Error::CATALOG[0],
+        ErrorDescriptor {
+            type_name: "Error",
+            variant_name: Some("FileOpen"),
+            doc: Some("The file could not be opened."),
+            display: Some("Could not open {path:?}"),
+            key: Some("file-open"),
+            fields: &[
+                FieldDescriptor {
+                    name: "path",
+                    ty: "PathBuf",
+                },
+                FieldDescriptor {
+                    name: "backtrace",
+                    ty: "Backtrace",
+                },
+            ],
+            source: Some("io::Error"),
+        },
+    );
+}
+
+#[test]
+fn documentation_and_name_are_used_for_default_display() {
+    let user_missing = &Error::CATALOG[1];
+    assert_eq!(
+        user_missing.display,
+        Some("No user with the given ID exists.")
+    );
+    assert_eq!(
+        user_missing.fields,
+        &[
+            FieldDescriptor {
+                name: "id",
+                ty: "u32",
+            },
+            FieldDescriptor {
+                name: "location",
+                ty: "Location",
+            },
+        ],
+    );
+
+    let unit = &Error::CATALOG[2];
+    assert_eq!(unit.doc, None);
+    assert_eq!(unit.display, Some("Unit"));
+    assert!(unit.fields.is_empty());
+    assert_eq!(unit.source, None);
+}
+
+#[test]
+fn transparent_errors_have_no_display_template() {
+    let io = &Error::CATALOG[3];
+    assert_eq!(io.display, None);
+    assert_eq!(io.source, Some("io::Error"));
+}
+
+#[test]
+fn whatever_errors_describe_the_message() {
+    let whatever = &Error::CATALOG[4];
+    assert_eq!(whatever.display, Some("{message}"));
+    assert_eq!(whatever.fields[0].name, "message");
+    assert_eq!(whatever.source, Some("Option<Box<dyn std::error::Error>>"));
+}
+
+#[test]
+fn structs_are_described() {
+    let [timeout] = TimeoutError::CATALOG else {
+        panic!("Expected exactly one descriptor");
+    };
+
+    assert_eq!(timeout.type_name, "TimeoutError");
+    assert_eq!(timeout.variant_name, None);
+    assert_eq!(timeout.display, Some("Limit of {}ms exceeded"));
+}
+
+#[test]
+fn generic_errors_are_described() {
+    let wrapped = &GenericError::<i32>::CATALOG[0];
+    assert_eq!(wrapped.fields[0].ty, "T");
+}
+
+#[test]
+fn renders_markdown() {
+    let markdown = snafu::catalog::to_markdown(&Error::CATALOG[..2]);
+
+    assert_eq!(
+        markdown,
+        "## `Error::FileOpen`\n\
+         \n\
+         The file could not be opened.\n\
+         \n\
+         - **Message:** `Could not open {path:?}`\n\
+         - **Key:** `file-open`\n\
+         - **Source:** `io::Error`\n\
+         \n\
+         | Field | Type |\n\
+         |-------|------|\n\
+         | `path` | `PathBuf` |\n\
+         | `backtrace` | `Backtrace` |\n\
+         \n\
+         ## `Error::UserMissing`\n\
+         \n\
+         No user with the given ID exists.\n\
+         \n\
+         - **Message:** `No user with the given ID exists.`\n\
+         \n\
+         | Field | Type |\n\
+         |-------|------|\n\
+         | `id` | `u32` |\n\
+         | `location` | `Location` |\n",
+    );
+}
+
+#[test]
+fn renders_json() {
+    let json = snafu::catalog::to_json(&[Error::CATALOG[2], Error::CATALOG[3]]);
+
+    assert_eq!(
+        json,
+        r#"[
+  {"type": "Error", "variant": "Unit", "doc": null, "display": "Unit", "key": null, "fields": [], "source": null},
+  {"type": "Error", "variant": "Io", "doc": null, "display": null, "key": null, "fields": [], "source": "io::Error"}
+]
+"#,
+    );
+
+    let json = snafu::catalog::to_json(TimeoutError::CATALOG);
+    assert!(json.contains(r#""fields": [{"name": "limit", "type": "u64"}]"#));
+
+    assert_eq!(snafu::catalog::to_json(&[]), "[]\n");
+}
@@ -0,0 +1,182 @@
+use snafu::{prelude::*, ErrorCompat, FromString, Report, Shared, Whatever};
+use std::{error::Error as _, io, sync::Arc};
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Inner failed"))]
+struct InnerError {
+    source: io::Error,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(clone)]
+enum Error {
+    #[snafu(display("Could not load {key}"))]
+    Load {
+        key: String,
+        source: Shared<InnerError>,
+    },
+
+    #[snafu(display("Could not connect"))]
+    Connect {
+        source: Shared<dyn std::error::Error + Send + Sync>,
+    },
+
+    #[snafu(display("Cache is full"))]
+    Full,
+}
+
+fn inner() -> Result<(), InnerError> {
+    Err(io::Error::new(io::ErrorKind::Other, "disk full")).context(InnerSnafu)
+}
+
+#[test]
+fn shared_sources_are_constructed_from_the_original_error() {
+    let error = inner().context(LoadSnafu { key: "a" }).unwrap_err();
+    let clone = error.clone();
+
+    assert_eq!(error.to_string(), clone.to_string());
+    assert!(matches!(clone, Error::Load { ref key, .. } if key == "a"));
+}
+
+#[test]
+fn shared_sources_report_the_original_error_as_source() {
+    let error = inner().context(LoadSnafu { key: "a" }).unwrap_err();
+    let clone = error.clone();
+
+    let source = clone.source().expect("must have a source");
+    assert!(source.is::<InnerError>());
+    assert_eq!(source.to_string(), "Inner failed");
+
+    let source = source.source().expect("must have a source");
+    assert_eq!(source.to_string(), "disk full");
+}
+
+#[test]
+fn shared_trait_object_sources_are_constructed_from_boxes() {
+    let boxed: Box<dyn std::error::Error + Send + Sync> = "refused".into();
+    let error = Err::<(), _>(boxed).context(ConnectSnafu).unwrap_err();
+    let clone = error.clone();
+
+    assert_eq!(clone.source().unwrap().to_string(), "refused");
+}
+
+#[test]
+fn report_output_is_unchanged_by_cloning() {
+    let error = inner().context(LoadSnafu { key: "a" }).unwrap_err();
+    let clone = error.clone();
+
+    assert_eq!(
+        Report::from_error(error).to_string(),
+        Report::from_error(clone).to_string(),
+    );
+}
+
+#[test]
+fn variants_without_sources_can_be_cloned() {
+    let error = FullSnafu.build();
+
+    assert!(matches!(error.clone(), Error::Full));
+}
+
+mod structs {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(clone, display("Request {id} failed"))]
+    struct RequestError {
+        id: u32,
+        source: Shared<io::Error>,
+        backtrace: Arc<snafu::Backtrace>,
+    }
+
+    #[test]
+    fn structs_can_be_cloned() {
+        let error = Err::<(), _>(io::Error::new(io::ErrorKind::Other, "oops"))
+            .context(RequestSnafu { id: 1_u32 })
+            .unwrap_err();
+        let clone = error.clone();
+
+        assert_eq!(clone.id, 1);
+        assert!(clone.source().unwrap().is::<io::Error>());
+        assert!(Arc::ptr_eq(&error.backtrace, &clone.backtrace));
+        assert!(ErrorCompat::backtrace(&clone).is_some());
+    }
+}
+
+mod generics {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(clone)]
+    enum Error<T>
+    where
+        T: std::fmt::Debug + std::fmt::Display,
+    {
+        #[snafu(display("Bad value {value}"))]
+        Bad { value: T },
+    }
+
+    #[test]
+    fn generic_errors_can_be_cloned() {
+        let error: Error<i32> = BadSnafu { value: 42 }.build();
+
+        assert_eq!(error.clone().to_string(), "Bad value 42");
+    }
+}
+
+#[test]
+fn whatever_can_be_shared() {
+    let error = Whatever::with_source(
+        Box::new(io::Error::new(io::ErrorKind::Other, "disk full")),
+        "Cache fill failed".into(),
+    );
+    let shared = error.shared();
+    let clone = shared.clone();
+
+    assert_eq!(
+        Report::from_error(clone).to_string(),
+        "Cache fill failed\n\nCaused by this error:\n  1: disk full\n",
+    );
+    assert_eq!(shared.source().unwrap().to_string(), "disk full");
+}
+
+mod foreign {
+    use std::{fmt, sync::Arc};
+
+    #[derive(Debug)]
+    pub struct Shared<T>(pub Arc<T>);
+
+    impl<T> Clone for Shared<T> {
+        fn clone(&self) -> Self {
+            Self(Arc::clone(&self.0))
+        }
+    }
+
+    impl<T: fmt::Display> fmt::Display for Shared<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.fmt(f)
+        }
+    }
+
+    impl<T: std::error::Error> std::error::Error for Shared<T> {}
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(clone)]
+enum ForeignError {
+    #[snafu(display("Could not fetch"))]
+    Fetch { source: foreign::Shared<io::Error> },
+}
+
+#[test]
+fn other_types_named_shared_are_used_as_they_are() {
+    use snafu::IntoError;
+
+    let source = foreign::Shared(Arc::new(io::Error::new(io::ErrorKind::Other, "timeout")));
+    let error = FetchSnafu.into_error(source);
+    let clone = error.clone();
+
+    let source = clone.source().expect("The clone has no source");
+    assert!(source.is::<foreign::Shared<io::Error>>());
+    assert_eq!(source.to_string(), "timeout");
+}
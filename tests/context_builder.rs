@@ -0,0 +1,151 @@
+use snafu::prelude::*;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(context(builder), display("Could not open {} with mode {mode:o}", path.display()))]
+    OpenFile {
+        source: std::io::Error,
+        path: PathBuf,
+        mode: u32,
+        #[snafu(default)]
+        hint: Option<String>,
+    },
+
+    #[snafu(context(builder))]
+    Leaf {
+        id: u8,
+        #[snafu(default = 5)]
+        retries: u8,
+    },
+
+    #[snafu(context(builder))]
+    Empty,
+}
+
+fn open(path: &Path) -> Result<(), std::io::Error> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        path.display().to_string(),
+    ))
+}
+
+#[test]
+fn builder_interoperates_with_context() {
+    let path = Path::new("/etc/config");
+
+    let error = open(path)
+        .context(OpenFileSnafu::new().path(path.to_owned()).mode(0o644_u32))
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Could not open /etc/config with mode 644"
+    );
+    assert!(matches!(error, Error::OpenFile { hint: None, .. }));
+}
+
+#[test]
+fn builder_interoperates_with_with_context() {
+    let error = open(Path::new("x"))
+        .with_context(|_| {
+            OpenFileSnafu::new()
+                .mode(0o600_u32)
+                .hint(String::from("check permissions"))
+                .path("x")
+        })
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::OpenFile { hint: Some(ref h), mode: 0o600, .. } if h == "check permissions"
+    ));
+}
+
+#[test]
+fn builder_interoperates_with_fail() {
+    let error = LeafSnafu::new().id(1).fail::<()>().unwrap_err();
+
+    assert!(matches!(error, Error::Leaf { id: 1, retries: 5 }));
+}
+
+#[test]
+fn builder_interoperates_with_ensure() {
+    fn check(id: u8) -> Result<(), Error> {
+        ensure!(id < 10, LeafSnafu::new().id(id).retries(0));
+        Ok(())
+    }
+
+    assert!(check(1).is_ok());
+    assert!(matches!(check(42), Err(Error::Leaf { id: 42, retries: 0 })));
+}
+
+#[test]
+fn builder_without_fields() {
+    let error = EmptySnafu::new().build();
+
+    assert!(matches!(error, Error::Empty));
+}
+
+mod structs {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(context(builder), display("Request {id} failed"))]
+    struct RequestError {
+        id: u32,
+    }
+
+    #[test]
+    fn builder_works_on_structs() {
+        let error = RequestSnafu::new().id(7_u32).build();
+
+        assert_eq!(error.to_string(), "Request 7 failed");
+    }
+}
+
+mod generics {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(context(builder))]
+    struct GenericError<'a, T>
+    where
+        T: core::fmt::Debug,
+    {
+        value: T,
+        name: &'a str,
+        #[snafu(default)]
+        extra: Vec<T>,
+    }
+
+    #[test]
+    fn builder_supports_generic_fields() {
+        let error: GenericError<'_, i32> = GenericSnafu::new()
+            .value(1)
+            .name("one")
+            .extra(vec![2, 3])
+            .build();
+
+        assert_eq!(error.value, 1);
+        assert_eq!(error.name, "one");
+        assert_eq!(error.extra, [2, 3]);
+    }
+}
+
+mod named {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        #[snafu(context(builder), context(name(Renamed)))]
+        Original { id: u8 },
+    }
+
+    #[test]
+    fn builder_can_be_renamed() {
+        let error = Renamed::new().id(1).build();
+
+        assert!(matches!(error, Error::Original { id: 1 }));
+    }
+}
@@ -0,0 +1,200 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not connect to {host}"))]
+    Connect {
+        host: String,
+        #[snafu(default)]
+        hint: Option<String>,
+        #[snafu(default = 3)]
+        attempts: u8,
+    },
+
+    Parse {
+        source: std::num::ParseIntError,
+        #[snafu(default = String::from("<stdin>"))]
+        origin: String,
+        line: usize,
+    },
+
+    AllDefaulted {
+        #[snafu(default)]
+        count: usize,
+    },
+}
+
+#[test]
+fn defaulted_fields_are_omitted_from_leaf_selectors() {
+    let error = ConnectSnafu {
+        host: "example.com",
+    }
+    .build();
+
+    assert!(matches!(
+        error,
+        Error::Connect {
+            ref host,
+            hint: None,
+            attempts: 3,
+        } if host == "example.com"
+    ));
+}
+
+#[test]
+fn defaulted_fields_are_omitted_from_selectors_with_sources() {
+    let error = "nope"
+        .parse::<u8>()
+        .context(ParseSnafu { line: 42_usize })
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::Parse {
+            ref origin,
+            line: 42,
+            ..
+        } if origin == "<stdin>"
+    ));
+}
+
+#[test]
+fn selector_without_remaining_fields_is_a_unit_struct() {
+    fn check() -> Result<(), Error> {
+        ensure!(false, AllDefaultedSnafu);
+        Ok(())
+    }
+
+    assert!(matches!(check(), Err(Error::AllDefaulted { count: 0 })));
+}
+
+mod structs {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(display("Request {id} failed after {retries} retries"))]
+    struct RequestError {
+        id: u32,
+        #[snafu(default)]
+        retries: u32,
+    }
+
+    #[test]
+    fn defaulted_fields_work_on_structs() {
+        let error = RequestSnafu { id: 7_u32 }.build();
+
+        assert_eq!(error.to_string(), "Request 7 failed after 0 retries");
+    }
+}
+
+mod generics {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    struct GenericError<T>
+    where
+        T: core::fmt::Debug,
+    {
+        value: T,
+        #[snafu(default)]
+        extra: Vec<T>,
+    }
+
+    #[test]
+    fn defaulted_fields_may_use_generic_types() {
+        let error: GenericError<i32> = GenericSnafu { value: 1 }.build();
+
+        assert_eq!(error.value, 1);
+        assert!(error.extra.is_empty());
+    }
+
+    #[test]
+    fn defaulted_fields_with_generic_types_can_be_set() {
+        let error: GenericError<i32> = GenericSnafu { value: 1 }.extra(vec![2, 3]).build();
+
+        assert_eq!(error.extra, [2, 3]);
+    }
+}
+
+mod setters {
+    use super::*;
+
+    #[test]
+    fn defaulted_fields_can_be_set_on_leaf_selectors() {
+        let error = ConnectSnafu {
+            host: "example.com",
+        }
+        .attempts(5)
+        .hint(Some(String::from("check the firewall")))
+        .build();
+
+        assert!(matches!(
+            error,
+            Error::Connect {
+                hint: Some(ref hint),
+                attempts: 5,
+                ..
+            } if hint == "check the firewall"
+        ));
+    }
+
+    #[test]
+    fn unset_defaulted_fields_keep_their_default() {
+        let error: Result<(), _> = ConnectSnafu { host: "a" }.hint(None).fail();
+
+        assert!(matches!(
+            error,
+            Err(Error::Connect {
+                hint: None,
+                attempts: 3,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn defaulted_fields_can_be_set_on_selectors_with_sources() {
+        let error = "nope"
+            .parse::<u8>()
+            .context(ParseSnafu { line: 1_usize }.origin(String::from("config.toml")))
+            .unwrap_err();
+
+        assert!(matches!(
+            error,
+            Error::Parse {
+                ref origin,
+                line: 1,
+                ..
+            } if origin == "config.toml"
+        ));
+    }
+
+    #[test]
+    fn defaulted_fields_can_be_set_on_unit_selectors() {
+        let error = AllDefaultedSnafu.count(2_usize).build();
+
+        assert!(matches!(error, Error::AllDefaulted { count: 2 }));
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(from)]
+    struct ReadError {
+        source: std::num::ParseIntError,
+        #[snafu(default = "<stdin>")]
+        path: &'static str,
+    }
+
+    #[test]
+    fn selectors_of_from_errors_still_set_defaulted_fields() {
+        fn parse(s: &str) -> Result<u8, ReadError> {
+            Ok(s.parse()?)
+        }
+
+        fn parse_file(s: &str) -> Result<u8, ReadError> {
+            s.parse().context(ReadSnafu.path("config.toml"))
+        }
+
+        assert_eq!(parse("x").unwrap_err().path, "<stdin>");
+        assert_eq!(parse_file("x").unwrap_err().path, "config.toml");
+    }
+}
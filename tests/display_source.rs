@@ -0,0 +1,159 @@
+use snafu::{prelude::*, ErrorCompat, FromString, IntoError, Report};
+use std::num::ParseIntError;
+
+#[derive(Debug, Snafu)]
+#[snafu(display_source(append))]
+enum AppendError {
+    #[snafu(display("Could not parse {input:?}"))]
+    Parse {
+        source: ParseIntError,
+        input: String,
+    },
+
+    #[snafu(context(false))]
+    Io { source: std::io::Error },
+
+    #[snafu(display("Nothing to parse"))]
+    Empty,
+
+    #[snafu(display("Could not read the port ({source})"))]
+    Port { source: ParseIntError },
+
+    #[snafu(display("Could not read the {}", describe(source)))]
+    Described { source: ParseIntError },
+
+    #[snafu(transparent)]
+    Wrapped { source: NeverError },
+
+    #[snafu(whatever, display("{message}"))]
+    Whatever {
+        message: String,
+        #[snafu(source(from(Box<dyn std::error::Error>, Some)))]
+        source: Option<Box<dyn std::error::Error>>,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display_source(never))]
+enum NeverError {
+    #[snafu(display("Could not parse the config"))]
+    Config { source: ParseIntError },
+
+    /// Could not parse {input}
+    Documented {
+        source: ParseIntError,
+        input: String,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display_source(append), display("Could not load"))]
+struct StructError {
+    source: ParseIntError,
+}
+
+fn describe(e: &ParseIntError) -> String {
+    format!("number: {}", e)
+}
+
+fn parse_error() -> ParseIntError {
+    "nope".parse::<u8>().unwrap_err()
+}
+
+#[test]
+fn append_adds_the_source_message() {
+    let e = ParseSnafu { input: "nope" }.into_error(parse_error());
+    assert_eq!(
+        e.to_string(),
+        r#"Could not parse "nope": invalid digit found in string"#,
+    );
+    assert_eq!(e.display_includes_source(), Some(true));
+
+    let e = StructError {
+        source: parse_error(),
+    };
+    assert_eq!(
+        e.to_string(),
+        "Could not load: invalid digit found in string",
+    );
+}
+
+#[test]
+fn append_skips_formats_that_already_use_the_source() {
+    let e = PortSnafu.into_error(parse_error());
+    assert_eq!(
+        e.to_string(),
+        "Could not read the port (invalid digit found in string)",
+    );
+    assert_eq!(e.display_includes_source(), Some(true));
+
+    let e = DescribedSnafu.into_error(parse_error());
+    assert_eq!(
+        e.to_string(),
+        "Could not read the number: invalid digit found in string",
+    );
+}
+
+#[test]
+fn append_uses_the_variant_name_without_a_display_format() {
+    let e = AppendError::from(std::io::Error::new(std::io::ErrorKind::Other, "oops"));
+    assert_eq!(e.to_string(), "Io: oops");
+}
+
+#[test]
+fn append_leaves_other_variants_alone() {
+    let e = EmptySnafu.build();
+    assert_eq!(e.to_string(), "Nothing to parse");
+    assert_eq!(e.display_includes_source(), Some(false));
+
+    let e = AppendError::from(ConfigSnafu.into_error(parse_error()));
+    assert_eq!(e.to_string(), "Could not parse the config");
+    assert_eq!(e.display_includes_source(), None);
+}
+
+#[test]
+fn append_adds_the_source_message_of_whatever_variants_when_present() {
+    let e = AppendError::without_source("Oops".to_owned());
+    assert_eq!(e.to_string(), "Oops");
+
+    let e = AppendError::with_source(Box::new(parse_error()), "Oops".to_owned());
+    assert_eq!(e.to_string(), "Oops: invalid digit found in string");
+    assert_eq!(e.display_includes_source(), Some(true));
+
+    let e = "nope"
+        .parse::<u8>()
+        .whatever_context::<_, AppendError>("Could not parse")
+        .unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "Could not parse: invalid digit found in string"
+    );
+}
+
+#[test]
+fn never_leaves_the_source_message_out() {
+    let e = ConfigSnafu.into_error(parse_error());
+    assert_eq!(e.to_string(), "Could not parse the config");
+    assert_eq!(e.display_includes_source(), Some(false));
+
+    let e = DocumentedSnafu { input: "nope" }.into_error(parse_error());
+    assert_eq!(e.to_string(), "Could not parse nope");
+}
+
+#[test]
+fn report_does_not_clean_messages_that_never_include_the_source() {
+    // Cleaning would remove the end of this message as it matches
+    // the message of the source.
+    let source = "nope".parse::<u8>().unwrap_err();
+    let input = source.to_string();
+    let e = DocumentedSnafu { input }.into_error(source);
+
+    let report = Report::from_error(e).with_error_compat().to_string();
+    assert_eq!(
+        report,
+        "Could not parse invalid digit found in string\n\
+         \n\
+         Caused by this error:\n  \
+         1: invalid digit found in string\n",
+    );
+}
@@ -0,0 +1,78 @@
+use snafu::prelude::*;
+use std::{fmt, path::PathBuf, time::Duration};
+
+fn upper<T: AsRef<str>>(value: &T, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&value.as_ref().to_uppercase())
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("Could not write {size} to {path}"))]
+    Write {
+        #[snafu(display_with = snafu::fmt::bytes)]
+        size: usize,
+        #[snafu(display_with = snafu::fmt::path)]
+        path: PathBuf,
+    },
+
+    #[snafu(display("Gave up after {elapsed} ({attempts} attempts)"))]
+    Timeout {
+        #[snafu(display_with = snafu::fmt::duration)]
+        elapsed: Duration,
+        #[snafu(display_with = |v: &u8, f: &mut fmt::Formatter<'_>| write!(f, "#{}", v))]
+        attempts: u8,
+    },
+
+    #[snafu(display("{name} is {name:with(upper)}; {{not:with(upper)}}"))]
+    Shout { name: String },
+
+    #[snafu(display("Positional {} and assigned {size}", size, size = size + 1))]
+    Explicit {
+        #[snafu(display_with = snafu::fmt::bytes)]
+        size: u64,
+    },
+
+    /// Missing {keys}
+    Documented {
+        #[snafu(display_with = snafu::fmt::list)]
+        keys: Vec<&'static str>,
+    },
+}
+
+#[test]
+fn fields_use_their_formatting_function() {
+    let e = WriteSnafu {
+        size: 2048usize,
+        path: "/tmp/out",
+    }
+    .build();
+    assert_eq!(e.to_string(), "Could not write 2.0 KiB to /tmp/out");
+
+    let e = TimeoutSnafu {
+        elapsed: Duration::from_secs(90),
+        attempts: 3u8,
+    }
+    .build();
+    assert_eq!(e.to_string(), "Gave up after 1m 30s (#3 attempts)");
+}
+
+#[test]
+fn inline_functions_apply_to_a_single_use() {
+    let e = ShoutSnafu { name: "alice" }.build();
+    assert_eq!(e.to_string(), "alice is ALICE; {not:with(upper)}");
+}
+
+#[test]
+fn explicit_arguments_are_not_changed() {
+    let e = ExplicitSnafu { size: 4096u64 }.build();
+    assert_eq!(e.to_string(), "Positional 4096 and assigned 4097");
+}
+
+#[test]
+fn doc_comments_use_the_formatting_function() {
+    let e = DocumentedSnafu {
+        keys: vec!["a", "b"],
+    }
+    .build();
+    assert_eq!(e.to_string(), "Missing a, b");
+}
@@ -20,3 +20,130 @@ fn accepts_trailing_commas() {
     }
     let _ = example(42);
 }
+
+mod comparisons {
+    use snafu::prelude::*;
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        Mismatch { left: i32, right: i32 },
+        Unexpected,
+    }
+
+    fn equal(a: i32, b: i32) -> Result<(), Error> {
+        ensure_eq!(a, b, |left, right| MismatchSnafu {
+            left: *left,
+            right: *right,
+        });
+        Ok(())
+    }
+
+    fn not_equal(a: i32, b: i32) -> Result<(), Error> {
+        ensure_ne!(a, b, UnexpectedSnafu);
+        Ok(())
+    }
+
+    #[test]
+    fn ensure_eq_exposes_both_operands() {
+        assert!(equal(1, 1).is_ok());
+        assert!(matches!(
+            equal(1, 2),
+            Err(Error::Mismatch { left: 1, right: 2 })
+        ));
+    }
+
+    #[test]
+    fn ensure_ne_fails_when_equal() {
+        assert!(not_equal(1, 2).is_ok());
+        assert!(matches!(not_equal(1, 1), Err(Error::Unexpected)));
+    }
+
+    #[test]
+    fn operands_are_not_consumed() {
+        fn example(a: String, b: String) -> Result<String, Error> {
+            ensure_eq!(a, b, UnexpectedSnafu);
+            Ok(a + &b)
+        }
+
+        assert_eq!(example("a".into(), "a".into()).unwrap(), "aa");
+    }
+}
+
+mod unwrapping {
+    use snafu::prelude::*;
+
+    #[derive(Debug, Snafu)]
+    enum Error {
+        Missing { key: &'static str },
+        Parsing { source: std::num::ParseIntError },
+    }
+
+    fn lookup(v: Option<i32>) -> Result<i32, Error> {
+        let v = ensure_some!(v, MissingSnafu { key: "v" });
+        Ok(v * 2)
+    }
+
+    fn parse(v: &str) -> Result<i32, Error> {
+        let v: i32 = ensure_ok!(v.parse(), ParsingSnafu);
+        Ok(v * 2)
+    }
+
+    #[test]
+    fn ensure_some_evaluates_to_the_value() {
+        assert_eq!(lookup(Some(21)).unwrap(), 42);
+        assert!(matches!(lookup(None), Err(Error::Missing { key: "v" })));
+    }
+
+    #[test]
+    fn ensure_ok_attaches_the_source() {
+        assert_eq!(parse("21").unwrap(), 42);
+        assert!(matches!(parse("nope"), Err(Error::Parsing { .. })));
+    }
+}
+
+mod whatever {
+    use snafu::{prelude::*, Whatever};
+
+    fn equal(a: i32, b: i32) -> Result<(), Whatever> {
+        ensure_whatever_eq!(a, b, |left, right| "{left} != {right}");
+        Ok(())
+    }
+
+    fn equal_plain(a: i32, b: i32) -> Result<(), Whatever> {
+        ensure_whatever_eq!(a, b, "Not equal: {}", a + b);
+        Ok(())
+    }
+
+    fn not_equal(a: i32, b: i32) -> Result<(), Whatever> {
+        ensure_whatever_ne!(a, b, |left, _| { "Both were {left}" });
+        Ok(())
+    }
+
+    fn some(v: Option<i32>) -> Result<i32, Whatever> {
+        Ok(ensure_whatever_some!(v, "Missing value"))
+    }
+
+    fn ok(v: &str) -> Result<i32, Whatever> {
+        Ok(ensure_whatever_ok!(v.parse::<i32>(), "Bad value {v}"))
+    }
+
+    #[test]
+    fn comparisons_produce_messages() {
+        assert!(equal(1, 1).is_ok());
+        assert_eq!(equal(1, 2).unwrap_err().to_string(), "1 != 2");
+        assert_eq!(equal_plain(1, 2).unwrap_err().to_string(), "Not equal: 3");
+        assert!(not_equal(1, 2).is_ok());
+        assert_eq!(not_equal(3, 3).unwrap_err().to_string(), "Both were 3");
+    }
+
+    #[test]
+    fn unwrapping_produces_messages() {
+        assert_eq!(some(Some(1)).unwrap(), 1);
+        assert_eq!(some(None).unwrap_err().to_string(), "Missing value");
+        assert_eq!(ok("1").unwrap(), 1);
+
+        let e = ok("x").unwrap_err();
+        assert_eq!(e.to_string(), "Bad value x");
+        assert!(std::error::Error::source(&e).is_some());
+    }
+}
@@ -0,0 +1,100 @@
+use snafu::{
+    exit_code::{ExitCode, ExitCodeProvider},
+    prelude::*,
+    Report,
+};
+use std::process::Termination;
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(exit_code = 3)]
+    Config,
+
+    #[snafu(exit_code = ExitCode::SUCCESS)]
+    Nothing,
+
+    Unmarked,
+
+    Wrapping {
+        source: InnerError,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(exit_code = 65)]
+struct InnerError {
+    id: u8,
+}
+
+#[derive(Debug, Snafu)]
+enum GenericError<T> {
+    #[snafu(exit_code = 4)]
+    Wrapped { value: T },
+}
+
+#[test]
+fn variants_provide_their_exit_code() {
+    assert_eq!(ConfigSnafu.build().exit_code(), Some(ExitCode::from(3)));
+    assert_eq!(NothingSnafu.build().exit_code(), Some(ExitCode::SUCCESS));
+    assert_eq!(UnmarkedSnafu.build().exit_code(), None);
+}
+
+#[test]
+fn structs_provide_their_exit_code() {
+    let e = InnerSnafu { id: 1 }.build();
+    assert_eq!(e.exit_code(), Some(ExitCode::from(65)));
+}
+
+#[test]
+fn generic_errors_provide_their_exit_code() {
+    let e: GenericError<i32> = WrappedSnafu { value: 42 }.build();
+    assert_eq!(e.exit_code(), Some(ExitCode::from(4)));
+}
+
+#[test]
+fn variants_without_an_exit_code_use_the_exit_code_of_their_source() {
+    let e = InnerSnafu { id: 1 }
+        .fail::<()>()
+        .context(WrappingSnafu)
+        .unwrap_err();
+
+    assert_eq!(e.exit_code(), Some(ExitCode::from(65)));
+}
+
+#[test]
+fn report_uses_the_exit_code_of_the_error() {
+    let report = Report::from_error(ConfigSnafu.build()).with_exit_code();
+    assert_eq!(report.report(), ExitCode::from(3));
+
+    let report = Report::from_error(UnmarkedSnafu.build()).with_exit_code();
+    assert_eq!(report.report(), ExitCode::FAILURE);
+}
+
+#[test]
+fn report_without_the_exit_code_uses_failure() {
+    let report = Report::from_error(ConfigSnafu.build());
+    assert_eq!(report.report(), ExitCode::FAILURE);
+}
+
+#[test]
+fn report_macro_uses_the_exit_code_of_the_error() {
+    #[snafu::report]
+    fn nested() -> Result<(), Error> {
+        InnerSnafu { id: 1 }.fail::<()>().context(WrappingSnafu)
+    }
+
+    assert_eq!(nested().report(), ExitCode::from(65));
+}
+
+#[test]
+fn report_macro_works_with_errors_without_exit_codes() {
+    #[derive(Debug, Snafu)]
+    struct PlainError;
+
+    #[snafu::report]
+    fn plain() -> Result<(), PlainError> {
+        PlainSnafu.fail()
+    }
+
+    assert_eq!(plain().report(), ExitCode::FAILURE);
+}
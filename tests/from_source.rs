@@ -0,0 +1,116 @@
+use snafu::{prelude::*, Location};
+use std::{io, num::ParseIntError};
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(from, context(builder), display("Could not parse {origin}"))]
+    Parse {
+        source: ParseIntError,
+        #[snafu(default = String::from("<stdin>"))]
+        origin: String,
+        #[snafu(default)]
+        line: Option<usize>,
+    },
+
+    #[snafu(from)]
+    Io {
+        source: io::Error,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    #[snafu(from)]
+    Boxed {
+        #[snafu(source(from(String, Into::into)))]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(from)]
+struct StructError {
+    source: ParseIntError,
+    #[snafu(default = 1)]
+    attempts: u8,
+}
+
+#[derive(Debug, Snafu)]
+enum GenericError<T>
+where
+    T: std::fmt::Debug + Default + 'static,
+{
+    #[snafu(from)]
+    Wrapped {
+        source: ParseIntError,
+        #[snafu(default)]
+        value: T,
+    },
+}
+
+fn parse(s: &str) -> Result<u8, Error> {
+    Ok(s.parse()?)
+}
+
+#[test]
+fn question_mark_fills_in_the_defaults() {
+    let error = parse("nope").unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::Parse {
+            ref origin,
+            line: None,
+            ..
+        } if origin == "<stdin>"
+    ));
+}
+
+#[test]
+fn context_selector_is_still_available() {
+    let error = "nope"
+        .parse::<u8>()
+        .context(
+            ParseSnafu::new()
+                .origin("config.toml".to_owned())
+                .line(Some(3)),
+        )
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::Parse {
+            ref origin,
+            line: Some(3),
+            ..
+        } if origin == "config.toml"
+    ));
+}
+
+#[test]
+fn implicit_fields_are_generated() {
+    let line = line!() + 1;
+    let error = Error::from(io::Error::new(io::ErrorKind::Other, "oops"));
+
+    match error {
+        Error::Io { location, .. } => assert_eq!(location.line(), line),
+        _ => panic!("Wrong variant: {:?}", error),
+    }
+}
+
+#[test]
+fn source_transformations_are_applied() {
+    let error = Error::from(String::from("boom"));
+    assert!(matches!(error, Error::Boxed { ref source } if source.to_string() == "boom"));
+}
+
+#[test]
+fn structs_can_be_created_from_the_source() {
+    let error = StructError::from("nope".parse::<u8>().unwrap_err());
+    assert_eq!(error.attempts, 1);
+}
+
+#[test]
+fn generic_errors_can_be_created_from_the_source() {
+    let error = GenericError::<i32>::from("nope".parse::<u8>().unwrap_err());
+    assert!(matches!(error, GenericError::Wrapped { value: 0, .. }));
+}
@@ -0,0 +1,123 @@
+use snafu::{i18n::Args, prelude::*, IntoError, Report};
+use std::{io, path::PathBuf};
+
+#[derive(Debug, Snafu)]
+#[snafu(display(key = "config-invalid", "Configuration {name} is invalid"))]
+struct InnerError {
+    name: String,
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display(key = "file-not-found", "Could not open {}", path.display()))]
+    FileNotFound { path: PathBuf, source: io::Error },
+
+    #[snafu(display(key = "config-load", "Could not load the configuration"))]
+    ConfigLoad { source: InnerError },
+
+    /// Nothing to see here
+    #[snafu(display(key = "undocumented"))]
+    KeyOnly { attempts: u8 },
+
+    #[snafu(display(key = "untranslated", "Still in English"))]
+    Untranslated,
+}
+
+fn german(key: &str, args: &Args<'_>) -> Option<String> {
+    let template = match key {
+        "config-invalid" => "Konfiguration {name} ist ungültig",
+        "file-not-found" => "Datei {path} konnte nicht geöffnet werden",
+        "config-load" => "Konfiguration konnte nicht geladen werden",
+        "undocumented" => "Nach {attempts} Versuchen aufgegeben",
+        _ => return None,
+    };
+    Some(args.interpolate(template))
+}
+
+fn file_not_found() -> Error {
+    let source = io::Error::new(io::ErrorKind::NotFound, "not found");
+    FileNotFoundSnafu { path: "/etc/app" }.into_error(source)
+}
+
+#[test]
+fn display_without_catalog_uses_literal_text() {
+    let error = file_not_found();
+    assert_eq!(error.to_string(), "Could not open /etc/app");
+
+    let error = KeyOnlySnafu { attempts: 3 }.build();
+    assert_eq!(error.to_string(), "Nothing to see here");
+}
+
+#[test]
+fn report_with_catalog_localizes_each_error_in_the_chain() {
+    let error = InnerSnafu { name: "app" }
+        .fail::<()>()
+        .context(ConfigLoadSnafu)
+        .unwrap_err();
+
+    let report = Report::from_error(error).with_catalog(german);
+
+    assert_eq!(
+        report.to_string(),
+        "Konfiguration konnte nicht geladen werden\n\
+         \n\
+         Caused by this error:\n  \
+         1: Konfiguration app ist ungültig\n",
+    );
+}
+
+#[test]
+fn fields_without_display_are_passed_with_debug() {
+    let report = Report::from_error(file_not_found()).with_catalog(german);
+
+    assert_eq!(
+        report.to_string(),
+        "Datei \"/etc/app\" konnte nicht geöffnet werden\n\
+         \n\
+         Caused by this error:\n  \
+         1: not found\n",
+    );
+}
+
+#[test]
+fn missing_keys_fall_back_to_the_literal_text() {
+    let report = Report::from_error(UntranslatedSnafu.build()).with_catalog(german);
+    assert_eq!(report.to_string(), "Still in English\n");
+}
+
+#[test]
+fn key_without_format_uses_catalog() {
+    let report = Report::from_error(KeyOnlySnafu { attempts: 3 }.build()).with_catalog(german);
+    assert_eq!(report.to_string(), "Nach 3 Versuchen aufgegeben\n");
+}
+
+#[test]
+fn catalog_receives_named_fields() {
+    let catalog = |key: &str, args: &Args<'_>| {
+        let names: Vec<_> = args.iter().map(|a| a.name()).collect();
+        Some(format!("{key}: {names:?}"))
+    };
+
+    let error = InnerSnafu { name: "app" }.build();
+    let report = Report::from_error(error).with_catalog(catalog);
+
+    assert_eq!(report.to_string(), "config-invalid: [\"name\"]\n");
+}
+
+#[test]
+fn catalog_is_only_active_while_rendering_the_report() {
+    let report = Report::from_error(UntranslatedSnafu.build()).with_catalog(german);
+    let _ = report.to_string();
+
+    let error = InnerSnafu { name: "app" }.build();
+    assert_eq!(error.to_string(), "Configuration app is invalid");
+}
+
+#[test]
+fn interpolate_handles_escapes_and_unknown_names() {
+    let error = InnerSnafu { name: "app" }.build();
+    let catalog = |_: &str, args: &Args<'_>| Some(args.interpolate("{{{name}}} {other} }"));
+    let report = Report::from_error(error).with_catalog(catalog);
+
+    assert_eq!(report.to_string(), "{app} {other} }\n");
+}
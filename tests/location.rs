@@ -239,3 +239,291 @@ mod track_caller {
         );
     }
 }
+
+mod every_construction_path {
+    use super::*;
+    use snafu::{FromString, IntoError};
+
+    #[derive(Debug, Snafu)]
+    enum LeafError {
+        #[snafu(context(builder))]
+        Leaf {
+            #[snafu(default = 3)]
+            retries: u8,
+            #[snafu(implicit)]
+            location: Location,
+        },
+    }
+
+    #[derive(Debug, Snafu)]
+    enum WrapError {
+        Context {
+            source: LeafError,
+            #[snafu(implicit)]
+            location: Location,
+        },
+
+        #[snafu(context(false))]
+        NoContext {
+            source: std::num::ParseIntError,
+            #[snafu(implicit)]
+            location: Location,
+        },
+
+        #[snafu(transparent)]
+        Transparent {
+            #[snafu(source(from(std::io::Error, Box::new)))]
+            source: Box<std::io::Error>,
+            #[snafu(implicit)]
+            location: Location,
+        },
+
+        #[snafu(from)]
+        FromWithDefaults {
+            source: std::str::Utf8Error,
+            #[snafu(default)]
+            detail: Option<String>,
+            #[snafu(implicit)]
+            location: Location,
+        },
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(transparent)]
+    struct GenericSourceError {
+        #[snafu(source(from(generic)))]
+        source: LeafError,
+        #[snafu(implicit)]
+        location: Location,
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(whatever, display("{message}"))]
+    struct Whatever {
+        #[snafu(source(from(Box<dyn std::error::Error>, Some)))]
+        source: Option<Box<dyn std::error::Error>>,
+        message: String,
+        #[snafu(implicit)]
+        location: Location,
+    }
+
+    fn location(e: &WrapError) -> Location {
+        match e {
+            WrapError::Context { location, .. }
+            | WrapError::NoContext { location, .. }
+            | WrapError::Transparent { location, .. }
+            | WrapError::FromWithDefaults { location, .. } => location,
+        }
+    }
+
+    fn io_error() -> std::io::Error {
+        std::io::Error::new(std::io::ErrorKind::Other, "oops")
+    }
+
+    fn bad_utf8() -> std::str::Utf8Error {
+        let bytes = vec![0xff];
+        std::str::from_utf8(&bytes).unwrap_err()
+    }
+
+    #[test]
+    fn builder_build() {
+        let (e, line) = (LeafSnafu::new().build(), line!());
+        let LeafError::Leaf { location, .. } = e;
+        assert_eq!(location.line(), line, "Actual location: {}", location);
+    }
+
+    #[test]
+    fn builder_fail() {
+        let (e, line) = (LeafSnafu::new().fail::<()>(), line!());
+        let LeafError::Leaf { location, .. } = e.unwrap_err();
+        assert_eq!(location.line(), line, "Actual location: {}", location);
+    }
+
+    #[test]
+    fn into_error() {
+        let source = LeafSnafu::new().build();
+        let (e, line) = (ContextSnafu.into_error(source), line!());
+        assert_eq!(
+            location(&e).line(),
+            line,
+            "Actual location: {}",
+            location(&e)
+        );
+    }
+
+    #[test]
+    fn context_false_with_question_mark() {
+        fn x() -> Result<u8, WrapError> {
+            Ok("nope".parse::<u8>()?)
+        }
+        let line = line!() - 2;
+
+        let e = x().unwrap_err();
+        assert_eq!(
+            location(&e).line(),
+            line,
+            "Actual location: {}",
+            location(&e)
+        );
+    }
+
+    #[test]
+    fn context_false_with_from() {
+        let source = "nope".parse::<u8>().unwrap_err();
+        let (e, line) = (WrapError::from(source), line!());
+        assert_eq!(
+            location(&e).line(),
+            line,
+            "Actual location: {}",
+            location(&e)
+        );
+    }
+
+    #[test]
+    fn transparent_with_transformed_source_and_question_mark() {
+        fn x() -> Result<(), WrapError> {
+            Err(io_error())?;
+            Ok(())
+        }
+        let line = line!() - 3;
+
+        let e = x().unwrap_err();
+        assert_eq!(
+            location(&e).line(),
+            line,
+            "Actual location: {}",
+            location(&e)
+        );
+    }
+
+    #[test]
+    fn transparent_with_into() {
+        let (e, line) = (WrapError::from(io_error()), line!());
+        assert_eq!(
+            location(&e).line(),
+            line,
+            "Actual location: {}",
+            location(&e)
+        );
+
+        let (e, line): (WrapError, _) = (io_error().into(), line!());
+        assert_eq!(
+            location(&e).line(),
+            line,
+            "Actual location: {}",
+            location(&e)
+        );
+    }
+
+    #[test]
+    fn transparent_with_generic_source() {
+        fn x() -> Result<(), GenericSourceError> {
+            LeafSnafu::new().fail()?;
+            Ok(())
+        }
+        let line = line!() - 3;
+
+        let e = x().unwrap_err();
+        assert_eq!(e.location.line(), line, "Actual location: {}", e.location);
+    }
+
+    #[test]
+    fn from_with_default_fields() {
+        fn x() -> Result<(), WrapError> {
+            Err(bad_utf8())?;
+            Ok(())
+        }
+        let line = line!() - 3;
+
+        let e = x().unwrap_err();
+        assert_eq!(
+            location(&e).line(),
+            line,
+            "Actual location: {}",
+            location(&e)
+        );
+    }
+
+    #[test]
+    fn from_string_without_source() {
+        let (e, line) = (Whatever::without_source("bang".into()), line!());
+        assert_eq!(e.location.line(), line, "Actual location: {}", e.location);
+    }
+
+    #[test]
+    fn from_string_with_source() {
+        let source = io_error().into();
+        let (e, line) = (Whatever::with_source(source, "bang".into()), line!());
+        assert_eq!(e.location.line(), line, "Actual location: {}", e.location);
+    }
+
+    #[test]
+    fn ensure_whatever() {
+        fn x() -> Result<(), Whatever> {
+            ensure_whatever!(false, "bang");
+            Ok(())
+        }
+        let line = line!() - 3;
+
+        let e = x().unwrap_err();
+        assert_eq!(e.location.line(), line, "Actual location: {}", e.location);
+    }
+
+    struct HandWrittenSnafu;
+
+    impl IntoError<WrapError> for HandWrittenSnafu {
+        type Source = LeafError;
+
+        fn into_error(self, source: Self::Source) -> WrapError {
+            ContextSnafu.into_error(source)
+        }
+    }
+
+    #[test]
+    fn hand_written_into_error() {
+        let source = LeafSnafu::new().build();
+        let (e, line) = (HandWrittenSnafu.into_error(source), line!());
+        assert_eq!(
+            location(&e).line(),
+            line,
+            "Actual location: {}",
+            location(&e)
+        );
+
+        let (e, line) = (LeafSnafu::new().fail::<()>(), line!());
+        let e = e.context(HandWrittenSnafu).unwrap_err();
+        assert_eq!(
+            location(&e).line(),
+            line + 1,
+            "Actual location: {}",
+            location(&e)
+        );
+    }
+
+    #[derive(Debug)]
+    struct Caller(Location);
+
+    impl snafu::GenerateImplicitData for Caller {
+        fn generate() -> Self {
+            Caller(std::panic::Location::caller())
+        }
+
+        fn generate_with_source(_source: &(dyn std::error::Error + 'static)) -> Self {
+            Caller(std::panic::Location::caller())
+        }
+    }
+
+    #[derive(Debug, Snafu)]
+    struct HandWrittenImplicitError {
+        source: LeafError,
+        #[snafu(implicit)]
+        caller: Caller,
+    }
+
+    #[test]
+    fn hand_written_generate_with_source() {
+        let source = LeafSnafu::new().build();
+        let (e, line) = (HandWrittenImplicitSnafu.into_error(source), line!());
+        assert_eq!(e.caller.0.line(), line, "Actual location: {}", e.caller.0);
+    }
+}
@@ -0,0 +1,136 @@
+use snafu::{prelude::*, ErrorCompat, IntoError, Location, LocationTrace, Report};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    Connect {
+        #[snafu(implicit)]
+        trace: LocationTrace,
+    },
+}
+
+#[derive(Debug, Snafu)]
+enum OuterError {
+    Startup {
+        source: InnerError,
+        #[snafu(implicit)]
+        trace: LocationTrace,
+    },
+
+    #[snafu(transparent)]
+    Forwarded {
+        source: InnerError,
+        #[snafu(implicit)]
+        trace: LocationTrace,
+    },
+}
+
+#[derive(Debug, Snafu)]
+enum TopError {
+    #[snafu(display("Could not run"))]
+    Run { source: OuterError },
+
+    #[snafu(transparent)]
+    Passthrough { source: OuterError },
+}
+
+#[derive(Debug, Snafu)]
+struct LocatedError {
+    #[snafu(implicit)]
+    location: Location,
+}
+
+#[derive(Debug, Snafu)]
+struct FromLocatedError {
+    source: LocatedError,
+    #[snafu(implicit)]
+    trace: LocationTrace,
+}
+
+fn lines(trace: Option<&LocationTrace>) -> Vec<u32> {
+    let trace = trace.expect("The error did not have a location trace");
+    trace.locations().iter().map(|l| l.line()).collect()
+}
+
+#[test]
+fn a_new_error_has_one_location() {
+    let (e, line) = (ConnectSnafu.build(), line!());
+
+    assert_eq!(lines(e.location_trace()), [line]);
+    assert_eq!(e.location_trace().unwrap().first().file(), file!());
+}
+
+#[test]
+fn each_context_call_adds_a_location() {
+    let (r, inner_line) = (ConnectSnafu.fail::<()>(), line!());
+    let (r, outer_line) = (r.context(StartupSnafu), line!());
+    let e = r.unwrap_err();
+
+    assert_eq!(lines(e.location_trace()), [inner_line, outer_line]);
+}
+
+#[test]
+fn transparent_variants_add_the_location_of_the_question_mark() {
+    fn inner() -> Result<(), InnerError> {
+        ConnectSnafu.fail()
+    }
+
+    fn outer() -> Result<(), OuterError> {
+        inner()?;
+        Ok(())
+    }
+
+    let e = outer().unwrap_err();
+    let trace = e.location_trace().unwrap();
+
+    assert_eq!(trace.locations().len(), 2);
+    assert_eq!(trace.first().line(), line!() - 12);
+    assert_eq!(trace.last().line(), line!() - 9);
+}
+
+#[test]
+fn report_shows_the_trace_through_variants_without_one() {
+    let (r, inner_line) = (ConnectSnafu.fail::<()>(), line!());
+    let (r, outer_line) = (r.context(StartupSnafu), line!());
+    let e = TopError::from(r.unwrap_err());
+
+    assert_eq!(lines(e.location_trace()), [inner_line, outer_line]);
+
+    let report = Report::from_error(e).with_error_compat().to_string();
+    let expected = format!(
+        "\nLocation trace (oldest first):\n  \
+         1: {file}:{inner_line}:41\n  \
+         2: {file}:{outer_line}:30\n",
+        file = file!(),
+    );
+    assert!(
+        report.contains(&expected),
+        "{:?} did not contain {:?}",
+        report,
+        expected,
+    );
+
+    // The report finds the trace of a source through the registry.
+    snafu::registry::register::<OuterError>();
+    let e = RunSnafu.into_error(StartupSnafu.into_error(ConnectSnafu.build()));
+    let report = Report::from_error(e).with_error_compat().to_string();
+    assert!(report.contains("Location trace (oldest first):\n  1: "));
+}
+
+#[test]
+fn sources_with_only_a_location_start_the_trace() {
+    let (source, source_line) = (LocatedSnafu.build(), line!());
+    let (e, line) = (FromLocatedSnafu.into_error(source), line!());
+
+    assert_eq!(lines(e.location_trace()), [source_line, line]);
+}
+
+#[test]
+fn display_joins_the_locations() {
+    let e = StartupSnafu.into_error(ConnectSnafu.build());
+    let trace = e.location_trace().unwrap();
+    let [first, last] = trace.locations() else {
+        panic!("Expected two locations, got {:?}", trace);
+    };
+
+    assert_eq!(trace.to_string(), format!("{} -> {}", first, last));
+}
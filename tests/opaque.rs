@@ -154,3 +154,90 @@ mod with_generic_source {
         }
     }
 }
+
+mod with_exposed_methods {
+    use super::*;
+
+    mod inner {
+        use snafu::prelude::*;
+
+        #[derive(Debug, Copy, Clone, PartialEq)]
+        pub enum Kind {
+            NotFound,
+            Timeout,
+        }
+
+        pub trait Code {
+            fn code(&self) -> u16;
+        }
+
+        #[derive(Debug, Snafu)]
+        #[snafu(opaque(expose(kind -> Kind, is_retryable -> bool)))]
+        #[snafu(opaque(expose(code -> u16, path -> &str)))]
+        pub struct Error(InnerError);
+
+        #[derive(Debug, Snafu)]
+        enum InnerError {
+            #[snafu(display("Could not find {path}"))]
+            Missing { path: String },
+
+            #[snafu(display("Timed out"))]
+            Slow,
+        }
+
+        impl InnerError {
+            fn kind(&self) -> Kind {
+                match self {
+                    InnerError::Missing { .. } => Kind::NotFound,
+                    InnerError::Slow => Kind::Timeout,
+                }
+            }
+
+            fn is_retryable(&self) -> bool {
+                matches!(self, InnerError::Slow)
+            }
+
+            fn path(&self) -> &str {
+                match self {
+                    InnerError::Missing { path } => path,
+                    InnerError::Slow => "",
+                }
+            }
+        }
+
+        impl Code for InnerError {
+            fn code(&self) -> u16 {
+                match self {
+                    InnerError::Missing { .. } => 404,
+                    InnerError::Slow => 504,
+                }
+            }
+        }
+
+        pub fn missing() -> Result<(), Error> {
+            MissingSnafu { path: "/index" }.fail()?;
+            Ok(())
+        }
+
+        pub fn slow() -> Result<(), Error> {
+            SlowSnafu.fail()?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn methods_forward_to_the_inner_error() {
+        check::<inner::Error>();
+
+        let e = inner::missing().unwrap_err();
+        assert_eq!(e.kind(), inner::Kind::NotFound);
+        assert!(!e.is_retryable());
+        assert_eq!(e.code(), 404);
+        assert_eq!(e.path(), "/index");
+
+        let e = inner::slow().unwrap_err();
+        assert_eq!(e.kind(), inner::Kind::Timeout);
+        assert!(e.is_retryable());
+        assert_eq!(e.code(), 504);
+    }
+}
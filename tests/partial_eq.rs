@@ -0,0 +1,198 @@
+use snafu::{prelude::*, Backtrace, Location};
+use std::io;
+
+#[derive(Debug, PartialEq, Snafu)]
+#[snafu(display("Inner failed: {code}"))]
+struct InnerError {
+    code: u8,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(partial_eq)]
+enum Error {
+    Comparable {
+        name: String,
+        source: InnerError,
+    },
+
+    Displayable {
+        source: io::Error,
+    },
+
+    Located {
+        id: u8,
+        #[snafu(implicit)]
+        location: Location,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(whatever, display("{message}"))]
+    Whatever {
+        message: String,
+        #[snafu(source(from(Box<dyn std::error::Error>, Some)))]
+        source: Option<Box<dyn std::error::Error>>,
+    },
+}
+
+fn inner(code: u8) -> Result<(), InnerError> {
+    InnerSnafu { code }.fail()
+}
+
+fn io(message: &str) -> Result<(), io::Error> {
+    Err(io::Error::new(io::ErrorKind::Other, message))
+}
+
+#[test]
+fn user_fields_and_comparable_sources_are_compared() {
+    let a = inner(1).context(ComparableSnafu { name: "a" }).unwrap_err();
+
+    assert_eq!(
+        a,
+        inner(1).context(ComparableSnafu { name: "a" }).unwrap_err()
+    );
+    assert_ne!(
+        a,
+        inner(1).context(ComparableSnafu { name: "b" }).unwrap_err()
+    );
+    assert_ne!(
+        a,
+        inner(2).context(ComparableSnafu { name: "a" }).unwrap_err()
+    );
+}
+
+#[test]
+fn incomparable_sources_are_compared_by_display() {
+    let a = io("oops").context(DisplayableSnafu).unwrap_err();
+
+    assert_eq!(a, io("oops").context(DisplayableSnafu).unwrap_err());
+    assert_ne!(a, io("uh oh").context(DisplayableSnafu).unwrap_err());
+}
+
+#[test]
+fn backtraces_and_implicit_fields_are_ignored() {
+    let a = LocatedSnafu { id: 1 }.build();
+    let b = LocatedSnafu { id: 1 }.build();
+
+    assert_eq!(a, b);
+    assert_ne!(a, LocatedSnafu { id: 2 }.build());
+}
+
+#[test]
+fn different_variants_are_not_equal() {
+    let a = io("oops").context(DisplayableSnafu).unwrap_err();
+    let b = LocatedSnafu { id: 1 }.build();
+
+    assert_ne!(a, b);
+}
+
+#[test]
+fn optional_whatever_sources_are_compared() {
+    use snafu::FromString;
+
+    let a = Error::without_source("oops".into());
+    let b = Error::with_source(
+        Box::new(io::Error::new(io::ErrorKind::Other, "x")),
+        "oops".into(),
+    );
+    let c = Error::with_source(
+        Box::new(io::Error::new(io::ErrorKind::Other, "x")),
+        "oops".into(),
+    );
+
+    assert_eq!(a, Error::without_source("oops".into()));
+    assert_ne!(a, b);
+    assert_eq!(b, c);
+}
+
+mod structs {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(partial_eq)]
+    struct RequestError {
+        id: u32,
+        source: io::Error,
+        backtrace: Backtrace,
+    }
+
+    #[test]
+    fn structs_can_be_compared() {
+        let a = io("oops").context(RequestSnafu { id: 1_u32 }).unwrap_err();
+
+        assert_eq!(
+            a,
+            io("oops").context(RequestSnafu { id: 1_u32 }).unwrap_err()
+        );
+        assert_ne!(
+            a,
+            io("oops").context(RequestSnafu { id: 2_u32 }).unwrap_err()
+        );
+    }
+}
+
+mod generics {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(partial_eq)]
+    enum Error<S>
+    where
+        S: std::error::Error + 'static,
+    {
+        Wrapped { source: S },
+    }
+
+    #[test]
+    fn generic_sources_are_compared_with_partial_eq() {
+        let a: Error<InnerError> = inner(1).context(WrappedSnafu).unwrap_err();
+        let b: Error<InnerError> = inner(1).context(WrappedSnafu).unwrap_err();
+
+        assert_eq!(a, b);
+    }
+}
+
+mod eq {
+    use super::*;
+
+    fn requires_eq<T: Eq>(_: &T) {}
+
+    #[derive(Debug, Snafu)]
+    #[snafu(partial_eq(eq))]
+    enum Error {
+        Lookup {
+            name: String,
+            source: io::Error,
+        },
+
+        #[snafu(whatever, display("{message}"))]
+        Other {
+            message: String,
+        },
+    }
+
+    #[test]
+    fn eq_is_implemented_when_requested() {
+        let a = io("oops").context(LookupSnafu { name: "a" }).unwrap_err();
+        let b = io("oops").context(LookupSnafu { name: "a" }).unwrap_err();
+
+        requires_eq(&a);
+        assert_eq!(a, b);
+    }
+
+    #[derive(Debug, Snafu)]
+    #[snafu(partial_eq(eq))]
+    struct GenericError<T>
+    where
+        T: std::fmt::Debug + std::fmt::Display,
+    {
+        value: T,
+    }
+
+    #[test]
+    fn eq_is_implemented_for_generic_errors() {
+        let a: GenericError<i32> = GenericSnafu { value: 1 }.build();
+
+        requires_eq(&a);
+        assert_eq!(a, GenericSnafu { value: 1 }.build());
+    }
+}
@@ -105,3 +105,91 @@ fn has_a_backtrace() {
     let bt = ErrorCompat::backtrace(&e).expect("Must have a backtrace");
     assert!(bt.to_string().contains("has_a_backtrace"));
 }
+
+mod accessors {
+    use super::*;
+    use snafu::{FromString, WhateverLocal};
+    use std::convert::TryFrom;
+
+    fn nested() -> Whatever {
+        fn inner() -> Result<i32> {
+            let v = underlying(false).whatever_context("Inner failed")?;
+            Ok(v)
+        }
+
+        inner().whatever_context("Outer failed").unwrap_err()
+    }
+
+    #[test]
+    fn message_does_not_include_the_sources() {
+        let e = nested();
+        assert_eq!(e.message(), "Outer failed");
+    }
+
+    #[test]
+    fn source_ref_is_the_wrapped_error() {
+        let e = nested();
+        let source = e.source_ref().expect("Must have a source");
+        assert_eq!(source.to_string(), "Inner failed");
+
+        let e = Whatever::without_source("Alone".into());
+        assert!(e.source_ref().is_none());
+    }
+
+    #[test]
+    fn downcast_source_ref_walks_the_chain() {
+        let e = nested();
+        assert_eq!(
+            e.downcast_source_ref::<Whatever>().map(Whatever::message),
+            Some("Inner failed"),
+        );
+        assert!(e.downcast_source_ref::<UnderlyingError>().is_some());
+        assert!(e.downcast_source_ref::<std::io::Error>().is_none());
+    }
+
+    #[test]
+    fn into_source_returns_the_wrapped_error() {
+        let source = nested().into_source().expect("Must have a source");
+        let source = source.downcast::<Whatever>().expect("Must be a Whatever");
+        assert_eq!(source.message(), "Inner failed");
+    }
+
+    #[test]
+    fn chain_messages_includes_every_error() {
+        let messages: Vec<_> = nested().chain_messages().collect();
+        assert_eq!(
+            messages,
+            ["Outer failed", "Inner failed", "UnderlyingError"]
+        );
+    }
+
+    #[test]
+    fn converts_to_and_from_whatever_local() {
+        let local = WhateverLocal::from(nested());
+        assert_eq!(local.message(), "Outer failed");
+        assert!(local.downcast_source_ref::<UnderlyingError>().is_some());
+        assert!(format!("{:?}", local).contains("source: Some(Whatever {"));
+
+        let e = Whatever::try_from(local).expect("Must be Send and Sync");
+        let messages: Vec<_> = e.chain_messages().collect();
+        assert_eq!(
+            messages,
+            ["Outer failed", "Inner failed", "UnderlyingError"]
+        );
+    }
+
+    #[test]
+    fn whatever_local_without_a_source_converts_to_whatever() {
+        let local = WhateverLocal::without_source("Alone".into());
+        let e = Whatever::try_from(local).expect("Must be Send and Sync");
+        assert_eq!(e.message(), "Alone");
+    }
+
+    #[test]
+    fn whatever_local_with_a_local_source_does_not_convert() {
+        let local = WhateverLocal::with_source(Box::new(UnderlyingError), "Local".into());
+        let local = Whatever::try_from(local).expect_err("Source is not known to be Send");
+        assert_eq!(local.message(), "Local");
+        assert!(local.into_source().is_some());
+    }
+}
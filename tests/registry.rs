@@ -0,0 +1,105 @@
+use snafu::{prelude::*, Backtrace, ErrorCompat, Location, Report};
+
+#[derive(Debug, Snafu)]
+enum OuterError {
+    #[snafu(display("Could not load"))]
+    Load {
+        source: InnerError,
+        #[snafu(implicit)]
+        location: Location,
+    },
+
+    Unlocated,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Could not parse"))]
+struct InnerError {
+    #[snafu(implicit)]
+    backtrace: Backtrace,
+    #[snafu(implicit)]
+    location: Location,
+}
+
+#[derive(Debug, Snafu)]
+struct OpaqueError(InnerError);
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Never registered"))]
+struct UnregisteredError {
+    #[snafu(implicit)]
+    location: Location,
+}
+
+fn load() -> Result<(), OuterError> {
+    InnerSnafu.fail().context(LoadSnafu)
+}
+
+#[test]
+fn implicit_locations_are_available() {
+    let outer = load().unwrap_err();
+    let outer_location = outer.location().expect("Outer location missing");
+    assert_eq!(outer_location.file(), file!());
+
+    let inner = ErrorChain::source(&outer);
+    let inner_location = inner.location().expect("Inner location missing");
+    assert_eq!(inner_location.line(), outer_location.line());
+
+    assert_eq!(UnlocatedSnafu.build().location(), None);
+}
+
+#[test]
+fn implicit_fields_of_other_types_are_skipped() {
+    let e = InnerSnafu.build();
+    assert_eq!(e.location(), Some(e.location));
+}
+
+#[test]
+fn opaque_errors_delegate_the_location() {
+    let e = OpaqueError::from(InnerSnafu.build());
+    assert!(e.location().is_some());
+}
+
+#[test]
+fn report_includes_locations_of_registered_errors() {
+    snafu::registry::register::<InnerError>();
+
+    let outer = load().unwrap_err();
+    let outer_location = outer.location().unwrap();
+    let inner_location = ErrorChain::source(&outer).location().unwrap();
+
+    let report = Report::from_error(outer).with_error_compat().to_string();
+
+    assert!(
+        report.starts_with(&format!(
+            "Could not load ({outer_location})\n\
+             \n\
+             Caused by this error:\n  \
+             1: Could not parse ({inner_location})\n\
+             \n\
+             Backtrace:\n",
+        )),
+        "{}",
+        report,
+    );
+}
+
+#[test]
+fn report_omits_locations_of_unregistered_errors() {
+    let report = Report::from_error(UnregisteredSnafu.build()).to_string();
+    assert_eq!(report, "Never registered\n");
+}
+
+// Only used to reach the concrete inner error without downcasting
+trait ErrorChain {
+    fn source(&self) -> &InnerError;
+}
+
+impl ErrorChain for OuterError {
+    fn source(&self) -> &InnerError {
+        match self {
+            OuterError::Load { source, .. } => source,
+            OuterError::Unlocated => unreachable!(),
+        }
+    }
+}
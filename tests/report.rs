@@ -142,6 +142,21 @@ fn debug_and_display_are_the_same() {
     assert_eq!(display, debug);
 }
 
+#[test]
+fn works_with_errors_that_borrow() {
+    #[derive(Debug, Snafu)]
+    #[snafu(display("Could not parse {input}"))]
+    struct ParseError<'a> {
+        input: &'a str,
+    }
+
+    let input = String::from("nope");
+    let r = Report::from_error(ParseSnafu { input: &*input }.build());
+    let msg = r.to_string();
+
+    assert_eq!(msg, "Could not parse nope\n");
+}
+
 /// `Report as Termination` prints-out the "Error:" prefix.  Ensure that `Report as Display` does
 /// not also add such a prefix, to avoid printing-out "Error: Error: ...".
 #[test]
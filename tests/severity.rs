@@ -0,0 +1,75 @@
+use snafu::{prelude::*, Severity};
+
+#[derive(Debug, Snafu)]
+#[snafu(severity(Warning))]
+enum EnumDefault {
+    Inherited,
+
+    #[snafu(severity(Fatal))]
+    Overridden,
+}
+
+#[derive(Debug, Snafu)]
+enum VariantsOnly {
+    #[snafu(severity(Warning))]
+    Marked,
+
+    Unmarked,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(severity(Fatal))]
+struct StructError {
+    id: u8,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(severity(Warning))]
+enum GenericError<T> {
+    Wrapped { value: T },
+}
+
+#[test]
+fn variants_inherit_the_enum_severity() {
+    assert_eq!(InheritedSnafu.build().severity(), Severity::Warning);
+    assert_eq!(OverriddenSnafu.build().severity(), Severity::Fatal);
+}
+
+#[test]
+fn unmarked_variants_default_to_error() {
+    assert_eq!(MarkedSnafu.build().severity(), Severity::Warning);
+    assert_eq!(UnmarkedSnafu.build().severity(), Severity::Error);
+}
+
+#[test]
+fn structs_have_a_severity() {
+    assert_eq!(StructSnafu { id: 1 }.build().severity(), Severity::Fatal);
+}
+
+#[test]
+fn generic_errors_have_a_severity() {
+    let e: GenericError<i32> = WrappedSnafu { value: 42 }.build();
+    assert_eq!(e.severity(), Severity::Warning);
+}
+
+#[test]
+fn severities_are_ordered() {
+    assert!(Severity::Warning < Severity::Error);
+    assert!(Severity::Error < Severity::Fatal);
+    assert_eq!(Severity::Fatal.max(Severity::Warning), Severity::Fatal);
+}
+
+#[test]
+fn severities_are_displayed() {
+    assert_eq!(Severity::Warning.to_string(), "Warning");
+    assert_eq!(format!("{:>7}", Severity::Fatal), "  Fatal");
+}
+
+#[test]
+fn every_severity_exits_unsuccessfully() {
+    use std::process::ExitCode;
+
+    assert_eq!(ExitCode::from(Severity::Warning), ExitCode::FAILURE);
+    assert_eq!(ExitCode::from(Severity::Error), ExitCode::FAILURE);
+    assert_eq!(ExitCode::from(Severity::Fatal), ExitCode::from(2));
+}
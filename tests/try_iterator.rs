@@ -0,0 +1,121 @@
+use snafu::{prelude::*, Location};
+
+#[derive(Debug, Snafu)]
+enum InnerError {
+    #[snafu(display("Item {index} was bad"))]
+    Bad { index: usize },
+}
+
+#[derive(Debug, Snafu)]
+enum Error {
+    Parsing {
+        source: InnerError,
+        name: String,
+    },
+
+    #[snafu(whatever, display("{message}"))]
+    Other {
+        message: String,
+
+        #[snafu(source(from(Box<dyn std::error::Error>, Some)))]
+        source: Option<Box<dyn std::error::Error>>,
+    },
+}
+
+fn items() -> impl Iterator<Item = Result<usize, InnerError>> {
+    (0..4).map(|index| {
+        if index % 2 == 0 {
+            Ok(index)
+        } else {
+            BadSnafu { index }.fail()
+        }
+    })
+}
+
+#[test]
+fn context_wraps_each_error() {
+    let results: Vec<Result<usize, Error>> = items().context(ParsingSnafu { name: "x" }).collect();
+
+    assert_eq!(results.len(), 4);
+    assert!(matches!(results[0], Ok(0)));
+    assert!(matches!(&results[1], Err(Error::Parsing { name, .. }) if name == "x"));
+    assert!(matches!(results[2], Ok(2)));
+    assert!(matches!(
+        &results[3],
+        Err(Error::Parsing {
+            source: InnerError::Bad { index: 3 },
+            ..
+        })
+    ));
+}
+
+#[test]
+fn with_context_is_only_called_for_errors() {
+    let mut calls = 0;
+
+    let results: Vec<Result<usize, Error>> = items()
+        .with_context(|e| {
+            calls += 1;
+            ParsingSnafu {
+                name: e.to_string(),
+            }
+        })
+        .collect();
+
+    assert_eq!(calls, 2);
+    assert!(matches!(&results[1], Err(Error::Parsing { name, .. }) if name == "Item 1 was bad"));
+}
+
+#[test]
+fn whatever_context_wraps_each_error() {
+    let result: Result<Vec<usize>, Error> = items().whatever_context("Oh no!").collect();
+
+    let error = result.unwrap_err();
+    assert_eq!(error.to_string(), "Oh no!");
+}
+
+#[test]
+fn with_whatever_context_wraps_each_error() {
+    let result: Result<Vec<usize>, Error> = items()
+        .with_whatever_context(|e| format!("Wrapped: {e}"))
+        .collect();
+
+    let error = result.unwrap_err();
+    assert_eq!(error.to_string(), "Wrapped: Item 1 was bad");
+}
+
+#[test]
+fn errors_are_mapped_lazily() {
+    let mut iter = items().whatever_context::<_, Error>("Oh no!");
+
+    assert_eq!(iter.size_hint(), (4, Some(4)));
+    assert!(matches!(iter.next(), Some(Ok(0))));
+    assert!(matches!(iter.next(), Some(Err(_))));
+    assert!(matches!(iter.next(), Some(Ok(2))));
+}
+
+mod location {
+    use super::*;
+
+    #[derive(Debug, Snafu)]
+    struct LocatedError {
+        source: InnerError,
+        #[snafu(implicit)]
+        location: Location,
+    }
+
+    #[test]
+    fn location_is_where_the_iterator_is_advanced() {
+        let mut iter = items().skip(1).context(LocatedSnafu);
+
+        let base_line = line!();
+        let error = iter.next().unwrap().unwrap_err();
+
+        assert_eq!(
+            error.location.line(),
+            base_line + 1,
+            "Actual location: {}",
+            error.location,
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use snafu::{prelude::*, ChainCompat, Report};
+
+mod db {
+    use snafu::prelude::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(visibility(pub))]
+    pub enum Error {
+        #[snafu(display("The table {name} is locked"))]
+        Locked { name: String },
+
+        #[snafu(display("The query failed"))]
+        Query { source: std::io::Error },
+    }
+}
+
+mod net {
+    use snafu::prelude::*;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(display("The connection was reset"))]
+    pub struct Error;
+}
+
+#[snafu::wrap(db::Error, net::Error, std::num::ParseIntError, Utf8 = std::str::Utf8Error)]
+#[derive(Debug, Snafu)]
+enum Error {
+    #[snafu(display("The request was invalid"))]
+    InvalidRequest,
+}
+
+fn locked() -> Result<(), Error> {
+    db::LockedSnafu { name: "users" }.fail()?;
+    Ok(())
+}
+
+#[test]
+fn variants_are_named_after_the_wrapped_type() {
+    let e = locked().unwrap_err();
+    assert!(matches!(e, Error::Db { .. }));
+
+    let e = Error::from(net::Error);
+    assert!(matches!(e, Error::Net { .. }));
+
+    let e = Error::from("x".parse::<u8>().unwrap_err());
+    assert!(matches!(e, Error::ParseInt { .. }));
+
+    let bytes = vec![0xFF];
+    let e = Error::from(std::str::from_utf8(&bytes).unwrap_err());
+    assert!(matches!(e, Error::Utf8 { .. }));
+
+    let e = InvalidRequestSnafu.build();
+    assert_eq!(e.to_string(), "The request was invalid");
+}
+
+#[test]
+fn display_and_source_are_delegated() {
+    let e = locked().unwrap_err();
+    assert_eq!(e.to_string(), "The table users is locked");
+    assert!(std::error::Error::source(&e).is_none());
+}
+
+#[test]
+fn chain_does_not_repeat_the_wrapped_error() {
+    let io = std::io::Error::new(std::io::ErrorKind::Other, "disk full");
+    let e = Error::from(db::Error::Query { source: io });
+
+    let messages: Vec<_> = ChainCompat::new(&e).map(|e| e.to_string()).collect();
+    assert_eq!(messages, ["The query failed", "disk full"]);
+
+    assert_eq!(
+        Report::from_error(e).to_string(),
+        "The query failed\n\nCaused by this error:\n  1: disk full\n",
+    );
+}